@@ -24,7 +24,7 @@ use cosmic::{
     iced::{
         self,
         event::{self, wayland, PlatformSpecific},
-        window, Length,
+        keyboard, window, Length,
     },
     prelude::*,
     widget::{
@@ -37,6 +37,9 @@ use cosmic_settings_page::{self as page, section};
 use page::Entity;
 use std::{borrow::Cow, str::FromStr};
 
+/// Seconds before a toast notification is dismissed automatically.
+const TOAST_TIMEOUT: u64 = 5;
+
 #[allow(clippy::struct_excessive_bools)]
 #[allow(clippy::module_name_repetitions)]
 pub struct SettingsApp {
@@ -50,6 +53,8 @@ pub struct SettingsApp {
     search_id: cosmic::widget::Id,
     search_input: String,
     search_selections: Vec<(page::Entity, section::Entity)>,
+    toast: Option<(u32, String)>,
+    toast_id: u32,
 }
 
 impl SettingsApp {
@@ -73,6 +78,7 @@ impl SettingsApp {
             PageCommands::Users => self.pages.page_id::<system::users::Page>(),
             PageCommands::Wallpaper => self.pages.page_id::<desktop::wallpaper::Page>(),
             PageCommands::Workspaces => self.pages.page_id::<desktop::workspaces::Page>(),
+            PageCommands::ListThemes | PageCommands::ApplyTheme { .. } => None,
         }
     }
 
@@ -88,6 +94,7 @@ pub enum Message {
     DelayedInit(page::Entity),
     DesktopInfo,
     Error(String),
+    KeyPressed(keyboard::Key, keyboard::Modifiers),
     OpenContextDrawer(Cow<'static, str>),
     Page(page::Entity),
     PageMessage(crate::pages::Message),
@@ -99,6 +106,8 @@ pub enum Message {
     SearchSubmit,
     SetTheme(cosmic::theme::Theme),
     SetWindowTitle,
+    Toast(String),
+    ToastExpired(u32),
 }
 
 impl cosmic::Application for SettingsApp {
@@ -128,6 +137,8 @@ impl cosmic::Application for SettingsApp {
             search_id: cosmic::widget::Id::unique(),
             search_input: String::new(),
             search_selections: Vec::default(),
+            toast: None,
+            toast_id: 0,
         };
 
         let desktop_id = app.insert_page::<desktop::Page>().id();
@@ -218,7 +229,16 @@ impl cosmic::Application for SettingsApp {
             _ => None,
         });
 
-        Subscription::batch(vec![
+        // Keyboard shortcuts which were not captured by a focused widget.
+        let keyboard_events = event::listen_with(|event, status| match (event, status) {
+            (
+                iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }),
+                event::Status::Ignored,
+            ) => Some(Message::KeyPressed(key, modifiers)),
+            _ => None,
+        });
+
+        let mut subscriptions = vec![
             // Creates a channel that listens to messages from pages.
             // The sender is given back to the application so that it may pass it on.
             cosmic::iced::subscription::channel(
@@ -242,6 +262,7 @@ impl cosmic::Application for SettingsApp {
                 )))
             }),
             wayland_events,
+            keyboard_events,
             // Watch for changes to installed desktop entries
             desktop_files(0).map(|_| Message::DesktopInfo),
             // Watch for configuration changes to the panel.
@@ -272,7 +293,41 @@ impl cosmic::Application for SettingsApp {
                         pages::desktop::wallpaper::Message::UpdateState(update.config),
                     ))
                 }),
-        ])
+        ];
+
+        // Drive the appearance page's animations while they are running.
+        if let Some(page) = self.pages.page::<appearance::Page>() {
+            if page.is_animating() {
+                subscriptions.push(cosmic::iced::time::every(appearance::ANIMATION_FRAME).map(
+                    |_| {
+                        Message::PageMessage(pages::Message::Appearance(
+                            appearance::Message::AnimationTick,
+                        ))
+                    },
+                ));
+            }
+        }
+
+        // Import theme files which are dropped onto the appearance page.
+        if self.pages.page_id::<appearance::Page>() == Some(self.active_page) {
+            subscriptions.push(
+                event::listen_with(|event, _| match event {
+                    iced::Event::Window(_, window::Event::FileHovered(_)) => {
+                        Some(appearance::Message::FileHovered(true))
+                    }
+                    iced::Event::Window(_, window::Event::FilesHoveredLeft) => {
+                        Some(appearance::Message::FileHovered(false))
+                    }
+                    iced::Event::Window(_, window::Event::FileDropped(path)) => {
+                        Some(appearance::Message::FileDropped(path))
+                    }
+                    _ => None,
+                })
+                .map(|message| Message::PageMessage(pages::Message::Appearance(message))),
+            );
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -442,6 +497,38 @@ impl cosmic::Application for SettingsApp {
                 tracing::error!(error, "error occurred");
             }
 
+            Message::KeyPressed(key, modifiers) => {
+                if self.pages.page_id::<appearance::Page>() == Some(self.active_page) {
+                    if let Some(message) = appearance::shortcut(&key, modifiers) {
+                        if let Some(page) = self.pages.page_mut::<appearance::Page>() {
+                            return page.update(message).map(cosmic::app::Message::App);
+                        }
+                    }
+                }
+            }
+
+            Message::Toast(message) => {
+                self.toast_id = self.toast_id.wrapping_add(1);
+                self.toast = Some((self.toast_id, message));
+
+                let id = self.toast_id;
+                return cosmic::command::future(async move {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(TOAST_TIMEOUT)).await;
+                    Message::ToastExpired(id)
+                })
+                .map(cosmic::app::Message::App);
+            }
+
+            Message::ToastExpired(id) => {
+                if self
+                    .toast
+                    .as_ref()
+                    .map_or(false, |(toast_id, _)| *toast_id == id)
+                {
+                    self.toast = None;
+                }
+            }
+
             Message::RegisterSubscriptionSender(sender) => {
                 self.page_sender = Some(sender);
             }
@@ -492,6 +579,19 @@ impl cosmic::Application for SettingsApp {
             theme.cosmic().space_l()
         };
 
+        let page_view = if let Some((id, toast)) = self.toast.as_ref() {
+            column::with_capacity(2)
+                .push(crate::widget::toast(
+                    toast.as_str(),
+                    Message::ToastExpired(*id),
+                ))
+                .push(page_view)
+                .spacing(theme.cosmic().space_s())
+                .into()
+        } else {
+            page_view
+        };
+
         container(page_view)
             .max_width(800)
             .width(Length::Fill)
@@ -73,6 +73,10 @@ pub enum PageCommands {
     Wallpaper,
     /// Workspaces settings page
     Workspaces,
+    /// Print available icon themes, look profiles and appearance snapshots, one per line
+    ListThemes,
+    /// Apply a theme file, or a theme read from stdin if the path is `-`
+    ApplyTheme { path: std::path::PathBuf },
 }
 
 impl FromStr for PageCommands {
@@ -115,6 +119,16 @@ pub fn main() -> color_eyre::Result<()> {
 
     let args = Args::parse();
 
+    if let Some(PageCommands::ListThemes) = args.subcommand {
+        tokio::runtime::Runtime::new()?.block_on(pages::desktop::appearance::print_theme_list());
+        return Ok(());
+    }
+
+    if let Some(PageCommands::ApplyTheme { path }) = args.subcommand.as_ref() {
+        pages::desktop::appearance::apply_theme(path)?;
+        return Ok(());
+    }
+
     let settings = cosmic::app::Settings::default()
         .size_limits(Limits::NONE.min_width(360.0).min_height(300.0))
         .exit_on_close(false);
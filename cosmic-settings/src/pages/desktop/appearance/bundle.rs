@@ -0,0 +1,92 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::Srgb;
+use cosmic::cosmic_theme::ThemeBuilder;
+use serde::{Deserialize, Serialize};
+
+/// Light and dark themes shared together as a single file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThemeBundle {
+    pub light: ThemeBuilder,
+    pub dark: ThemeBuilder,
+    /// Accent of the light theme.
+    ///
+    /// Stored alongside the builders so that each mode keeps its own accent
+    /// even if the builders are edited or regenerated by other tools.
+    #[serde(default)]
+    pub light_accent: Option<Srgb>,
+    /// Accent of the dark theme.
+    #[serde(default)]
+    pub dark_accent: Option<Srgb>,
+    /// ID of the icon theme to use with this theme.
+    #[serde(default)]
+    pub icon_theme: Option<String>,
+}
+
+impl ThemeBundle {
+    /// Bundles the light and dark themes, recording the accent of each.
+    #[must_use]
+    pub fn new(light: ThemeBuilder, dark: ThemeBuilder, icon_theme: Option<String>) -> Self {
+        Self {
+            light_accent: light.accent,
+            dark_accent: dark.accent,
+            light,
+            dark,
+            icon_theme,
+        }
+    }
+
+    /// The light and dark theme builders, with their respective accents applied.
+    #[must_use]
+    pub fn into_builders(self) -> (ThemeBuilder, ThemeBuilder) {
+        let Self {
+            mut light,
+            mut dark,
+            light_accent,
+            dark_accent,
+            ..
+        } = self;
+
+        if light_accent.is_some() {
+            light.accent = light_accent;
+        }
+
+        if dark_accent.is_some() {
+            dark.accent = dark_accent;
+        }
+
+        (light, dark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accents_round_trip_to_their_own_mode() {
+        let light_accent = Srgb::new(0.9, 0.3, 0.1);
+        let dark_accent = Srgb::new(0.2, 0.5, 0.9);
+
+        let mut light = ThemeBuilder::light();
+        light.accent = Some(light_accent);
+        let mut dark = ThemeBuilder::dark();
+        dark.accent = Some(dark_accent);
+
+        let bundle = ThemeBundle::new(light, dark, None);
+        assert_eq!(bundle.light_accent, Some(light_accent));
+        assert_eq!(bundle.dark_accent, Some(dark_accent));
+
+        let serialized = ron::ser::to_string(&bundle).unwrap();
+        let mut bundle: ThemeBundle = ron::from_str(&serialized).unwrap();
+
+        // The recorded accents win over builders edited by other tools.
+        bundle.light.accent = None;
+        bundle.dark.accent = Some(light_accent);
+
+        let (light, dark) = bundle.into_builders();
+        assert_eq!(light.accent, Some(light_accent));
+        assert_eq!(dark.accent, Some(dark_accent));
+    }
+}
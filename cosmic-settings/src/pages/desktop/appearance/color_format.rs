@@ -0,0 +1,138 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::{FromColor, Hsl, Hsv, RgbHue, Srgb};
+
+/// A textual representation of a color, for copying it to the clipboard.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorFormat {
+    #[default]
+    Hex,
+    Rgb,
+    Hsl,
+}
+
+impl ColorFormat {
+    pub const ALL: [Self; 3] = [Self::Hex, Self::Rgb, Self::Hsl];
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Hex => fl!("hex"),
+            Self::Rgb => fl!("rgb"),
+            Self::Hsl => fl!("hsl"),
+        }
+    }
+
+    /// The color written in this format, such as `#3478f6`, `rgb(52,120,246)`, or
+    /// `hsl(220,91%,58%)`.
+    #[must_use]
+    pub fn format(self, color: Srgb) -> String {
+        match self {
+            Self::Hex => {
+                let Srgb {
+                    red, green, blue, ..
+                } = color.into_format::<u8>();
+                format!("#{red:02x}{green:02x}{blue:02x}")
+            }
+            Self::Rgb => {
+                let Srgb {
+                    red, green, blue, ..
+                } = color.into_format::<u8>();
+                format!("rgb({red},{green},{blue})")
+            }
+            Self::Hsl => {
+                let hsl = Hsl::from_color(color);
+                format!(
+                    "hsl({},{}%,{}%)",
+                    hsl.hue.into_positive_degrees().round() as u16 % 360,
+                    (hsl.saturation * 100.0).round() as u8,
+                    (hsl.lightness * 100.0).round() as u8
+                )
+            }
+        }
+    }
+}
+
+/// Components in which a color can be typed into a color picker.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorInputMode {
+    #[default]
+    Hsl,
+    Hsv,
+}
+
+impl ColorInputMode {
+    pub const ALL: [Self; 2] = [Self::Hsl, Self::Hsv];
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Hsl => fl!("hsl"),
+            Self::Hsv => fl!("hsv"),
+        }
+    }
+
+    /// Names of the hue, saturation, and lightness or value components.
+    #[must_use]
+    pub fn labels(self) -> [String; 3] {
+        let third = match self {
+            Self::Hsl => fl!("color-input", "lightness"),
+            Self::Hsv => fl!("color-input", "value"),
+        };
+
+        [
+            fl!("color-input", "hue"),
+            fl!("color-input", "saturation"),
+            third,
+        ]
+    }
+
+    /// The hue in degrees, followed by the saturation and lightness or value in percent.
+    ///
+    /// Components are shown to a tenth, which is fine enough for any 8-bit color to be
+    /// typed back in unchanged.
+    #[must_use]
+    pub fn components(self, color: Srgb) -> [String; 3] {
+        let (hue, saturation, third) = match self {
+            Self::Hsl => {
+                let hsl = Hsl::from_color(color);
+                (hsl.hue, hsl.saturation, hsl.lightness)
+            }
+            Self::Hsv => {
+                let hsv = Hsv::from_color(color);
+                (hsv.hue, hsv.saturation, hsv.value)
+            }
+        };
+
+        [
+            hue.into_positive_degrees(),
+            saturation * 100.0,
+            third * 100.0,
+        ]
+        .map(|component| format!("{component:.1}"))
+    }
+
+    /// The color with typed components, or `None` if a component is not a number.
+    ///
+    /// Saturation, lightness and value are clamped to their range, and hues wrap around.
+    #[must_use]
+    pub fn color(self, components: &[String; 3]) -> Option<Srgb> {
+        let mut values = [0.0f32; 3];
+        for (value, component) in values.iter_mut().zip(components) {
+            *value = component.trim().trim_end_matches(['°', '%']).parse().ok()?;
+        }
+
+        let [hue, saturation, third] = values;
+        let hue = RgbHue::from_degrees(hue.rem_euclid(360.0));
+        let (saturation, third) = (
+            (saturation / 100.0).clamp(0.0, 1.0),
+            (third / 100.0).clamp(0.0, 1.0),
+        );
+
+        Some(match self {
+            Self::Hsl => Srgb::from_color(Hsl::new(hue, saturation, third)),
+            Self::Hsv => Srgb::from_color(Hsv::new(hue, saturation, third)),
+        })
+    }
+}
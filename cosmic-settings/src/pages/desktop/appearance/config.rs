@@ -0,0 +1,846 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use cosmic::cosmic_config::{self, ConfigGet, ConfigSet};
+use cosmic::cosmic_theme::palette::Srgb;
+
+use super::contrast::ContrastStandard;
+use super::gradient::BackgroundGradient;
+use super::IconThemeSort;
+
+const NAME: &str = "com.system76.CosmicSettings.Appearance";
+const VERSION: u64 = 1;
+const CONTRAST_STANDARD: &str = "contrast-standard";
+const ICON_THEME_SORT: &str = "icon-theme-sort";
+const ICON_THEME_USAGE: &str = "icon-theme-usage";
+const DECORATION_ACCENT_ONLY: &str = "decoration-accent-only";
+const DARK_DECORATION_ACCENT: &str = "dark-decoration-accent";
+const LIGHT_DECORATION_ACCENT: &str = "light-decoration-accent";
+const DARK_SELECTION_COLOR: &str = "dark-selection-color";
+const LIGHT_SELECTION_COLOR: &str = "light-selection-color";
+const ICON_SCAN_EXCLUDES: &str = "icon-scan-excludes";
+const OVERWRITE_WITHOUT_CONFIRM: &str = "overwrite-without-confirm";
+const SAVED_ACCENTS: &str = "saved-accents";
+const MANUAL_LOCATION: &str = "manual-location";
+const DARK_BACKGROUND_GRADIENT: &str = "dark-background-gradient";
+const LIGHT_BACKGROUND_GRADIENT: &str = "light-background-gradient";
+const INTERFACE_FONT: &str = "interface-font";
+const MONOSPACE_FONT: &str = "monospace-font";
+const TEXT_SCALING_FACTOR: &str = "text-scaling-factor";
+const ICON_PREVIEW_SIZE: &str = "icon-preview-size";
+const WINDOW_HINT_OPACITY: &str = "window-hint-opacity";
+const WINDOW_HINT_COLOR: &str = "window-hint-color";
+const ACTIVE_LOOK_PROFILE: &str = "active-look-profile";
+const SEPARATE_MODE_ACCENTS: &str = "separate-mode-accents";
+const RECENT_THEME_FILES: &str = "recent-theme-files";
+const RECENT_COLORS: &str = "recent-colors";
+const SYNC_LAYOUT: &str = "sync-layout";
+const RECENT_COLORS_LIMIT: &str = "recent-colors-limit";
+const ICON_THEME_OVERRIDES: &str = "icon-theme-overrides";
+const GTK_THEME: &str = "gtk-theme";
+
+/// The most theme files remembered as recently imported or exported.
+const RECENT_THEME_FILES_LIMIT: usize = 8;
+/// The most recent colors remembered for each color picker, unless configured otherwise.
+const DEFAULT_RECENT_COLORS_LIMIT: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct Config {
+    context: Option<cosmic_config::Config>,
+    contrast_standard: ContrastStandard,
+    icon_theme_sort: IconThemeSort,
+    /// Seconds since the Unix epoch at which each icon theme was last selected.
+    icon_theme_usage: BTreeMap<String, u64>,
+    decoration_accent_only: bool,
+    /// The chosen accents of the dark and light themes while they are only applied to window
+    /// decorations.
+    dark_decoration_accent: Option<Srgb>,
+    light_decoration_accent: Option<Srgb>,
+    /// Override the accent-derived selection highlight colors of the dark and light themes.
+    dark_selection_color: Option<Srgb>,
+    light_selection_color: Option<Srgb>,
+    /// Directories which are skipped when searching for icon themes.
+    icon_scan_excludes: Vec<PathBuf>,
+    /// Whether exports replace existing files without asking first.
+    overwrite_without_confirm: bool,
+    /// Custom accent colors saved by the user for reuse.
+    saved_accents: Vec<Srgb>,
+    /// Latitude and longitude for sunrise and sunset when location services are unavailable.
+    manual_location: Option<(f64, f64)>,
+    /// Application background gradients of the dark and light themes.
+    dark_background_gradient: Option<BackgroundGradient>,
+    light_background_gradient: Option<BackgroundGradient>,
+    /// Font family of the interface, or `None` for the toolkit's default.
+    interface_font: Option<String>,
+    /// Font family of terminals and code, or `None` for the toolkit's default.
+    monospace_font: Option<String>,
+    /// Factor by which interface text is scaled, or `None` for unscaled text.
+    text_scaling_factor: Option<f64>,
+    /// Size of the icons previewed for each icon theme, or `None` for the default size.
+    icon_preview_size: Option<u16>,
+    /// Opacity of the active window hint, or `None` for an opaque hint.
+    window_hint_opacity: Option<f32>,
+    /// The chosen window hint color, before it is blended by its opacity.
+    window_hint_color: Option<Srgb>,
+    /// Name of the look profile which was last saved or switched to.
+    active_look_profile: Option<String>,
+    /// Whether the accents of the light and dark themes are edited side by side.
+    separate_mode_accents: bool,
+    /// Theme files which were recently imported or exported, most recent first.
+    recent_theme_files: Vec<PathBuf>,
+    /// Recently applied colors of each kind of color picker, most recent first.
+    recent_colors: BTreeMap<String, Vec<Srgb>>,
+    /// The most recent colors remembered for each kind of color picker.
+    recent_colors_limit: Option<usize>,
+    /// Whether roundness, gaps, and the active hint are shared by both color modes,
+    /// or `None` if they are shared by default.
+    sync_layout: Option<bool>,
+    /// Icon themes used by particular applications, by the desktop ID of the application.
+    icon_theme_overrides: BTreeMap<String, String>,
+    /// The GTK theme last chosen for GNOME applications.
+    gtk_theme: Option<String>,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        let mut config = Self::default();
+
+        let context = match cosmic_config::Config::new(NAME, VERSION) {
+            Ok(context) => context,
+            Err(why) => {
+                tracing::warn!(?why, "failed to get config");
+                return Self::default();
+            }
+        };
+
+        if let Ok(standard) = context.get::<ContrastStandard>(CONTRAST_STANDARD) {
+            config.contrast_standard = standard;
+        }
+
+        if let Ok(sort) = context.get::<IconThemeSort>(ICON_THEME_SORT) {
+            config.icon_theme_sort = sort;
+        }
+
+        if let Ok(usage) = context.get::<BTreeMap<String, u64>>(ICON_THEME_USAGE) {
+            config.icon_theme_usage = usage;
+        }
+
+        if let Ok(enabled) = context.get::<bool>(DECORATION_ACCENT_ONLY) {
+            config.decoration_accent_only = enabled;
+        }
+
+        if let Ok(accent) = context.get::<Option<Srgb>>(DARK_DECORATION_ACCENT) {
+            config.dark_decoration_accent = accent;
+        }
+
+        if let Ok(accent) = context.get::<Option<Srgb>>(LIGHT_DECORATION_ACCENT) {
+            config.light_decoration_accent = accent;
+        }
+
+        if let Ok(color) = context.get::<Option<Srgb>>(DARK_SELECTION_COLOR) {
+            config.dark_selection_color = color;
+        }
+
+        if let Ok(color) = context.get::<Option<Srgb>>(LIGHT_SELECTION_COLOR) {
+            config.light_selection_color = color;
+        }
+
+        if let Ok(excludes) = context.get::<Vec<PathBuf>>(ICON_SCAN_EXCLUDES) {
+            config.icon_scan_excludes = excludes;
+        }
+
+        if let Ok(enabled) = context.get::<bool>(OVERWRITE_WITHOUT_CONFIRM) {
+            config.overwrite_without_confirm = enabled;
+        }
+
+        if let Ok(accents) = context.get::<Vec<Srgb>>(SAVED_ACCENTS) {
+            config.saved_accents = accents;
+        }
+
+        if let Ok(location) = context.get::<Option<(f64, f64)>>(MANUAL_LOCATION) {
+            config.manual_location = location;
+        }
+
+        if let Ok(gradient) = context.get::<Option<BackgroundGradient>>(DARK_BACKGROUND_GRADIENT) {
+            config.dark_background_gradient = gradient;
+        }
+
+        if let Ok(gradient) = context.get::<Option<BackgroundGradient>>(LIGHT_BACKGROUND_GRADIENT) {
+            config.light_background_gradient = gradient;
+        }
+
+        if let Ok(family) = context.get::<Option<String>>(INTERFACE_FONT) {
+            config.interface_font = family;
+        }
+
+        if let Ok(family) = context.get::<Option<String>>(MONOSPACE_FONT) {
+            config.monospace_font = family;
+        }
+
+        if let Ok(factor) = context.get::<Option<f64>>(TEXT_SCALING_FACTOR) {
+            config.text_scaling_factor = factor;
+        }
+
+        if let Ok(size) = context.get::<Option<u16>>(ICON_PREVIEW_SIZE) {
+            config.icon_preview_size = size;
+        }
+
+        if let Ok(opacity) = context.get::<Option<f32>>(WINDOW_HINT_OPACITY) {
+            config.window_hint_opacity = opacity;
+        }
+
+        if let Ok(color) = context.get::<Option<Srgb>>(WINDOW_HINT_COLOR) {
+            config.window_hint_color = color;
+        }
+
+        if let Ok(name) = context.get::<Option<String>>(ACTIVE_LOOK_PROFILE) {
+            config.active_look_profile = name;
+        }
+
+        if let Ok(enabled) = context.get::<bool>(SEPARATE_MODE_ACCENTS) {
+            config.separate_mode_accents = enabled;
+        }
+
+        if let Ok(files) = context.get::<Vec<PathBuf>>(RECENT_THEME_FILES) {
+            config.recent_theme_files = files;
+        }
+
+        if let Ok(colors) = context.get::<BTreeMap<String, Vec<Srgb>>>(RECENT_COLORS) {
+            config.recent_colors = colors;
+        }
+
+        if let Ok(limit) = context.get::<Option<usize>>(RECENT_COLORS_LIMIT) {
+            config.recent_colors_limit = limit;
+        }
+
+        if let Ok(enabled) = context.get::<Option<bool>>(SYNC_LAYOUT) {
+            config.sync_layout = enabled;
+        }
+
+        if let Ok(overrides) = context.get::<BTreeMap<String, String>>(ICON_THEME_OVERRIDES) {
+            config.icon_theme_overrides = overrides;
+        }
+
+        if let Ok(theme) = context.get::<Option<String>>(GTK_THEME) {
+            config.gtk_theme = theme;
+        }
+
+        config.context = Some(context);
+
+        config
+    }
+
+    #[must_use]
+    pub fn contrast_standard(&self) -> ContrastStandard {
+        self.contrast_standard
+    }
+
+    /// Sets the contrast standard used by contrast warnings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_contrast_standard(
+        &mut self,
+        standard: ContrastStandard,
+    ) -> Result<(), cosmic_config::Error> {
+        self.contrast_standard = standard;
+        self.update(CONTRAST_STANDARD, &standard)
+    }
+
+    #[must_use]
+    pub fn icon_theme_sort(&self) -> IconThemeSort {
+        self.icon_theme_sort
+    }
+
+    /// Sets the order in which icon themes are displayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_icon_theme_sort(&mut self, sort: IconThemeSort) -> Result<(), cosmic_config::Error> {
+        self.icon_theme_sort = sort;
+        self.update(ICON_THEME_SORT, &sort)
+    }
+
+    /// When the icon theme was last selected, in seconds since the Unix epoch.
+    #[must_use]
+    pub fn icon_theme_last_used(&self, id: &str) -> Option<u64> {
+        self.icon_theme_usage.get(id).copied()
+    }
+
+    /// Records that an icon theme was just selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn icon_theme_used(&mut self, id: String) -> Result<(), cosmic_config::Error> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.icon_theme_usage.insert(id, now);
+        self.update(ICON_THEME_USAGE, &self.icon_theme_usage)
+    }
+
+    #[must_use]
+    pub fn decoration_accent_only(&self) -> bool {
+        self.decoration_accent_only
+    }
+
+    /// Sets whether the accent is only applied to window decorations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_decoration_accent_only(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), cosmic_config::Error> {
+        self.decoration_accent_only = enabled;
+        self.update(DECORATION_ACCENT_ONLY, &enabled)
+    }
+
+    #[must_use]
+    pub fn decoration_accent(&self, is_dark: bool) -> Option<Srgb> {
+        if is_dark {
+            self.dark_decoration_accent
+        } else {
+            self.light_decoration_accent
+        }
+    }
+
+    /// Stores the chosen accent of a theme mode while it is only applied to window decorations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_decoration_accent(
+        &mut self,
+        is_dark: bool,
+        accent: Option<Srgb>,
+    ) -> Result<(), cosmic_config::Error> {
+        let (key, field) = if is_dark {
+            (DARK_DECORATION_ACCENT, &mut self.dark_decoration_accent)
+        } else {
+            (LIGHT_DECORATION_ACCENT, &mut self.light_decoration_accent)
+        };
+
+        if *field == accent {
+            return Ok(());
+        }
+
+        *field = accent;
+        self.update(key, &accent)
+    }
+
+    #[must_use]
+    pub fn selection_color(&self, is_dark: bool) -> Option<Srgb> {
+        if is_dark {
+            self.dark_selection_color
+        } else {
+            self.light_selection_color
+        }
+    }
+
+    /// Sets the selection highlight color of a theme mode, or `None` to derive it from the
+    /// accent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_selection_color(
+        &mut self,
+        is_dark: bool,
+        color: Option<Srgb>,
+    ) -> Result<(), cosmic_config::Error> {
+        let (key, field) = if is_dark {
+            (DARK_SELECTION_COLOR, &mut self.dark_selection_color)
+        } else {
+            (LIGHT_SELECTION_COLOR, &mut self.light_selection_color)
+        };
+
+        if *field == color {
+            return Ok(());
+        }
+
+        *field = color;
+        self.update(key, &color)
+    }
+
+    #[must_use]
+    pub fn icon_scan_excludes(&self) -> &[PathBuf] {
+        &self.icon_scan_excludes
+    }
+
+    /// Skips a directory, and everything beneath it, when searching for icon themes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn add_icon_scan_exclude(&mut self, dir: PathBuf) -> Result<(), cosmic_config::Error> {
+        if self.icon_scan_excludes.contains(&dir) {
+            return Ok(());
+        }
+
+        self.icon_scan_excludes.push(dir);
+        self.update(ICON_SCAN_EXCLUDES, &self.icon_scan_excludes)
+    }
+
+    /// Searches a previously excluded directory for icon themes again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn remove_icon_scan_exclude(&mut self, dir: &Path) -> Result<(), cosmic_config::Error> {
+        self.icon_scan_excludes.retain(|excluded| excluded != dir);
+        self.update(ICON_SCAN_EXCLUDES, &self.icon_scan_excludes)
+    }
+
+    #[must_use]
+    pub fn overwrite_without_confirm(&self) -> bool {
+        self.overwrite_without_confirm
+    }
+
+    /// Sets whether exports replace existing files without asking first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_overwrite_without_confirm(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), cosmic_config::Error> {
+        self.overwrite_without_confirm = enabled;
+        self.update(OVERWRITE_WITHOUT_CONFIRM, &enabled)
+    }
+
+    #[must_use]
+    pub fn saved_accents(&self) -> &[Srgb] {
+        &self.saved_accents
+    }
+
+    /// Saves a custom accent color to the user's palette.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn add_saved_accent(&mut self, accent: Srgb) -> Result<(), cosmic_config::Error> {
+        if self.saved_accents.contains(&accent) {
+            return Ok(());
+        }
+
+        self.saved_accents.push(accent);
+        self.update(SAVED_ACCENTS, &self.saved_accents)
+    }
+
+    /// Removes a color from the user's palette.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn remove_saved_accent(&mut self, index: usize) -> Result<(), cosmic_config::Error> {
+        if index >= self.saved_accents.len() {
+            return Ok(());
+        }
+
+        self.saved_accents.remove(index);
+        self.update(SAVED_ACCENTS, &self.saved_accents)
+    }
+
+    #[must_use]
+    pub fn manual_location(&self) -> Option<(f64, f64)> {
+        self.manual_location
+    }
+
+    /// Sets the latitude and longitude used when location services are unavailable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_manual_location(
+        &mut self,
+        location: Option<(f64, f64)>,
+    ) -> Result<(), cosmic_config::Error> {
+        self.manual_location = location;
+        self.update(MANUAL_LOCATION, &location)
+    }
+
+    #[must_use]
+    pub fn background_gradient(&self, is_dark: bool) -> Option<&BackgroundGradient> {
+        if is_dark {
+            self.dark_background_gradient.as_ref()
+        } else {
+            self.light_background_gradient.as_ref()
+        }
+    }
+
+    /// Sets the application background gradient of a theme mode, or `None` for a solid color.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_background_gradient(
+        &mut self,
+        is_dark: bool,
+        gradient: Option<BackgroundGradient>,
+    ) -> Result<(), cosmic_config::Error> {
+        let (key, field) = if is_dark {
+            (DARK_BACKGROUND_GRADIENT, &mut self.dark_background_gradient)
+        } else {
+            (
+                LIGHT_BACKGROUND_GRADIENT,
+                &mut self.light_background_gradient,
+            )
+        };
+
+        *field = gradient;
+        let gradient = field.clone();
+        self.update(key, &gradient)
+    }
+
+    #[must_use]
+    pub fn interface_font(&self) -> Option<&str> {
+        self.interface_font.as_deref()
+    }
+
+    /// Sets the font family of the interface.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_interface_font(&mut self, family: String) -> Result<(), cosmic_config::Error> {
+        self.interface_font = Some(family);
+        self.update(INTERFACE_FONT, &self.interface_font)
+    }
+
+    #[must_use]
+    pub fn monospace_font(&self) -> Option<&str> {
+        self.monospace_font.as_deref()
+    }
+
+    /// Sets the font family of terminals and code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_monospace_font(&mut self, family: String) -> Result<(), cosmic_config::Error> {
+        self.monospace_font = Some(family);
+        self.update(MONOSPACE_FONT, &self.monospace_font)
+    }
+
+    #[must_use]
+    pub fn text_scaling_factor(&self) -> f64 {
+        self.text_scaling_factor.unwrap_or(1.0)
+    }
+
+    /// Sets the factor by which interface text is scaled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_text_scaling_factor(&mut self, factor: f64) -> Result<(), cosmic_config::Error> {
+        self.text_scaling_factor = Some(factor);
+        self.update(TEXT_SCALING_FACTOR, &self.text_scaling_factor)
+    }
+
+    #[must_use]
+    pub fn icon_preview_size(&self) -> Option<u16> {
+        self.icon_preview_size
+    }
+
+    /// Sets the size of the icons previewed for each icon theme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_icon_preview_size(&mut self, size: u16) -> Result<(), cosmic_config::Error> {
+        self.icon_preview_size = Some(size);
+        self.update(ICON_PREVIEW_SIZE, &self.icon_preview_size)
+    }
+
+    #[must_use]
+    pub fn window_hint_opacity(&self) -> f32 {
+        self.window_hint_opacity.unwrap_or(1.0)
+    }
+
+    /// Sets the opacity of the active window hint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_window_hint_opacity(&mut self, opacity: f32) -> Result<(), cosmic_config::Error> {
+        self.window_hint_opacity = Some(opacity);
+        self.update(WINDOW_HINT_OPACITY, &self.window_hint_opacity)
+    }
+
+    #[must_use]
+    pub fn window_hint_color(&self) -> Option<Srgb> {
+        self.window_hint_color
+    }
+
+    /// Sets the chosen window hint color, which is only written if it changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_window_hint_color(
+        &mut self,
+        color: Option<Srgb>,
+    ) -> Result<(), cosmic_config::Error> {
+        if self.window_hint_color == color {
+            return Ok(());
+        }
+
+        self.window_hint_color = color;
+        self.update(WINDOW_HINT_COLOR, &color)
+    }
+
+    #[must_use]
+    pub fn active_look_profile(&self) -> Option<&str> {
+        self.active_look_profile.as_deref()
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_active_look_profile(
+        &mut self,
+        name: Option<String>,
+    ) -> Result<(), cosmic_config::Error> {
+        self.active_look_profile = name;
+        self.update(ACTIVE_LOOK_PROFILE, &self.active_look_profile)
+    }
+
+    #[must_use]
+    pub fn separate_mode_accents(&self) -> bool {
+        self.separate_mode_accents
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_separate_mode_accents(&mut self, enabled: bool) -> Result<(), cosmic_config::Error> {
+        self.separate_mode_accents = enabled;
+        self.update(SEPARATE_MODE_ACCENTS, &enabled)
+    }
+
+    #[must_use]
+    pub fn recent_theme_files(&self) -> &[PathBuf] {
+        &self.recent_theme_files
+    }
+
+    /// Moves a theme file to the front of the recent theme files, forgetting the oldest
+    /// file once there are too many.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn add_recent_theme_file(&mut self, path: PathBuf) -> Result<(), cosmic_config::Error> {
+        self.recent_theme_files.retain(|file| *file != path);
+        self.recent_theme_files.insert(0, path);
+        self.recent_theme_files.truncate(RECENT_THEME_FILES_LIMIT);
+        self.update(RECENT_THEME_FILES, &self.recent_theme_files)
+    }
+
+    /// Forgets the recent theme files which no longer exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn prune_recent_theme_files(&mut self) -> Result<(), cosmic_config::Error> {
+        let len = self.recent_theme_files.len();
+        self.recent_theme_files.retain(|file| file.exists());
+
+        if self.recent_theme_files.len() == len {
+            return Ok(());
+        }
+
+        self.update(RECENT_THEME_FILES, &self.recent_theme_files)
+    }
+
+    #[must_use]
+    pub fn sync_layout(&self) -> bool {
+        self.sync_layout.unwrap_or(true)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_sync_layout(&mut self, enabled: bool) -> Result<(), cosmic_config::Error> {
+        self.sync_layout = Some(enabled);
+        self.update(SYNC_LAYOUT, &self.sync_layout)
+    }
+
+    /// Recently applied colors of a kind of color picker, most recent first.
+    #[must_use]
+    pub fn recent_colors(&self, kind: &str) -> &[Srgb] {
+        self.recent_colors.get(kind).map_or(&[], Vec::as_slice)
+    }
+
+    /// Moves a color to the front of the recent colors of a kind of color picker,
+    /// forgetting the oldest color once there are too many.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn add_recent_color(
+        &mut self,
+        kind: &str,
+        color: Srgb,
+    ) -> Result<(), cosmic_config::Error> {
+        let limit = self
+            .recent_colors_limit
+            .unwrap_or(DEFAULT_RECENT_COLORS_LIMIT);
+        let colors = self.recent_colors.entry(kind.to_owned()).or_default();
+        colors.retain(|recent| *recent != color);
+        colors.insert(0, color);
+        colors.truncate(limit);
+        self.update(RECENT_COLORS, &self.recent_colors)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn clear_recent_colors(&mut self, kind: &str) -> Result<(), cosmic_config::Error> {
+        if self.recent_colors.remove(kind).is_none() {
+            return Ok(());
+        }
+
+        self.update(RECENT_COLORS, &self.recent_colors)
+    }
+
+    /// Icon theme IDs used in place of the system icon theme, by application desktop ID.
+    #[must_use]
+    pub fn icon_theme_overrides(&self) -> &BTreeMap<String, String> {
+        &self.icon_theme_overrides
+    }
+
+    /// Gives an application its own icon theme, replacing any it already had.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_icon_theme_override(
+        &mut self,
+        app_id: String,
+        icon_theme: String,
+    ) -> Result<(), cosmic_config::Error> {
+        if self.icon_theme_overrides.get(&app_id) == Some(&icon_theme) {
+            return Ok(());
+        }
+
+        self.icon_theme_overrides.insert(app_id, icon_theme);
+        self.update(ICON_THEME_OVERRIDES, &self.icon_theme_overrides)
+    }
+
+    /// Returns an application to the system icon theme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn remove_icon_theme_override(&mut self, app_id: &str) -> Result<(), cosmic_config::Error> {
+        if self.icon_theme_overrides.remove(app_id).is_none() {
+            return Ok(());
+        }
+
+        self.update(ICON_THEME_OVERRIDES, &self.icon_theme_overrides)
+    }
+
+    #[must_use]
+    pub fn gtk_theme(&self) -> Option<&str> {
+        self.gtk_theme.as_deref()
+    }
+
+    /// Remembers the GTK theme chosen for GNOME applications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn set_gtk_theme(&mut self, theme: String) -> Result<(), cosmic_config::Error> {
+        self.gtk_theme = Some(theme);
+        self.update(GTK_THEME, &self.gtk_theme)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn clear_saved_accents(&mut self) -> Result<(), cosmic_config::Error> {
+        self.saved_accents.clear();
+        self.update(SAVED_ACCENTS, &self.saved_accents)
+    }
+
+    /// Forgets the settings which the page applies on top of the theme, such as the selection
+    /// color, window hint, background gradients, and fonts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn clear_theme_overrides(&mut self) -> Result<(), cosmic_config::Error> {
+        self.decoration_accent_only = false;
+        self.dark_decoration_accent = None;
+        self.light_decoration_accent = None;
+        self.dark_selection_color = None;
+        self.light_selection_color = None;
+        self.window_hint_color = None;
+        self.window_hint_opacity = None;
+        self.dark_background_gradient = None;
+        self.light_background_gradient = None;
+        self.interface_font = None;
+        self.monospace_font = None;
+
+        self.update(DECORATION_ACCENT_ONLY, &self.decoration_accent_only)?;
+        self.update(DARK_DECORATION_ACCENT, &self.dark_decoration_accent)?;
+        self.update(LIGHT_DECORATION_ACCENT, &self.light_decoration_accent)?;
+        self.update(DARK_SELECTION_COLOR, &self.dark_selection_color)?;
+        self.update(LIGHT_SELECTION_COLOR, &self.light_selection_color)?;
+        self.update(WINDOW_HINT_COLOR, &self.window_hint_color)?;
+        self.update(WINDOW_HINT_OPACITY, &self.window_hint_opacity)?;
+        self.update(DARK_BACKGROUND_GRADIENT, &self.dark_background_gradient)?;
+        self.update(LIGHT_BACKGROUND_GRADIENT, &self.light_background_gradient)?;
+        self.update(INTERFACE_FONT, &self.interface_font)?;
+        self.update(MONOSPACE_FONT, &self.monospace_font)
+    }
+
+    /// Forgets the recent colors of every kind of color picker.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the on-disk configuration could not be updated.
+    pub fn clear_all_recent_colors(&mut self) -> Result<(), cosmic_config::Error> {
+        self.recent_colors.clear();
+        self.update(RECENT_COLORS, &self.recent_colors)
+    }
+
+    fn update<V: serde::Serialize>(
+        &self,
+        key: &str,
+        value: &V,
+    ) -> Result<(), cosmic_config::Error> {
+        if let Some(context) = self.context.as_ref() {
+            context.set(key, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the selection color of a theme mode, without reading the rest of the settings.
+#[must_use]
+pub fn stored_selection_color(is_dark: bool) -> Option<Srgb> {
+    let key = if is_dark {
+        DARK_SELECTION_COLOR
+    } else {
+        LIGHT_SELECTION_COLOR
+    };
+
+    cosmic_config::Config::new(NAME, VERSION)
+        .ok()?
+        .get::<Option<Srgb>>(key)
+        .ok()
+        .flatten()
+}
@@ -0,0 +1,47 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::Srgb;
+use serde::{Deserialize, Serialize};
+
+/// WCAG conformance level that contrast warnings are checked against.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ContrastStandard {
+    #[default]
+    Aa,
+    Aaa,
+}
+
+impl ContrastStandard {
+    pub const ALL: [Self; 2] = [Self::Aa, Self::Aaa];
+
+    /// Minimum contrast ratio for normal text at this level.
+    #[must_use]
+    pub fn min_ratio(self) -> f32 {
+        match self {
+            Self::Aa => 4.5,
+            Self::Aaa => 7.0,
+        }
+    }
+
+    #[must_use]
+    pub fn passes(self, ratio: f32) -> bool {
+        ratio >= self.min_ratio()
+    }
+}
+
+/// WCAG 2.1 contrast ratio between two colors, from 1.0 to 21.0.
+#[must_use]
+pub fn contrast_ratio(fg: Srgb, bg: Srgb) -> f32 {
+    let fg = relative_luminance(fg);
+    let bg = relative_luminance(bg);
+    let (lighter, darker) = if fg > bg { (fg, bg) } else { (bg, fg) };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// WCAG 2.1 relative luminance of an sRGB color.
+fn relative_luminance(color: Srgb) -> f32 {
+    let linear = color.into_linear();
+    0.2126 * linear.red + 0.7152 * linear.green + 0.0722 * linear.blue
+}
@@ -0,0 +1,191 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use cosmic::iced_runtime::core::image::Handle as ImageHandle;
+
+/// Nominal sizes of the cursor previews.
+pub const PREVIEW_SIZES: [u32; 2] = [24, 48];
+
+/// Cursors tried, in order, for the preview of a theme.
+const PREVIEW_CURSORS: [&str; 3] = ["default", "left_ptr", "arrow"];
+
+/// Largest cursor image which will be decoded for a preview.
+const MAX_IMAGE_SIZE: u32 = 256;
+
+const XCURSOR_MAGIC: &[u8; 4] = b"Xcur";
+const XCURSOR_IMAGE_TYPE: u32 = 0xfffd_0002;
+
+#[derive(Clone, Debug)]
+pub struct CursorTheme {
+    /// Name of the directory of the theme, which is what toolkits refer to it by.
+    pub id: String,
+    pub name: String,
+    pub previews: Vec<ImageHandle>,
+}
+
+/// Finds the cursor themes in the given icon directories, along with the ID of the
+/// default cursor theme.
+pub async fn fetch(icon_dirs: Vec<PathBuf>) -> (Vec<CursorTheme>, Option<String>) {
+    tokio::task::spawn_blocking(move || {
+        let active = icon_dirs
+            .iter()
+            .rev()
+            .find_map(|dir| index_value(&dir.join("default"), "Inherits="))
+            .and_then(|inherits| inherits.split(',').next().map(str::to_owned));
+
+        (find(&icon_dirs), active)
+    })
+    .await
+    .unwrap_or_default()
+}
+
+fn find(icon_dirs: &[PathBuf]) -> Vec<CursorTheme> {
+    let mut themes: Vec<CursorTheme> = Vec::new();
+
+    for icon_dir in icon_dirs {
+        let Ok(read_dir) = std::fs::read_dir(icon_dir) else {
+            continue;
+        };
+
+        for entry in read_dir.filter_map(Result::ok) {
+            let path = entry.path();
+            let Some(id) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+
+            // `default` only points to the theme which is used by default.
+            if id == "default" || themes.iter().any(|theme| theme.id == id) {
+                continue;
+            }
+
+            let cursors = path.join("cursors");
+            if !cursors.is_dir() {
+                continue;
+            }
+
+            let previews = PREVIEW_CURSORS
+                .iter()
+                .find_map(|name| std::fs::read(cursors.join(name)).ok())
+                .map(|data| {
+                    PREVIEW_SIZES
+                        .iter()
+                        .filter_map(|&size| decode(&data, size))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            themes.push(CursorTheme {
+                name: index_value(&path, "Name=").unwrap_or_else(|| id.clone()),
+                id,
+                previews,
+            });
+        }
+    }
+
+    themes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    themes
+}
+
+/// Reads a key from the `[Icon Theme]` section of the `index.theme` of a theme directory.
+fn index_value(theme_dir: &Path, key: &str) -> Option<String> {
+    let contents = std::fs::read_to_string(theme_dir.join("index.theme")).ok()?;
+    let mut in_icon_theme = false;
+
+    contents
+        .lines()
+        .filter(|line| {
+            if line.starts_with('[') {
+                in_icon_theme = line.trim() == "[Icon Theme]";
+                return false;
+            }
+
+            in_icon_theme
+        })
+        .find_map(|line| line.strip_prefix(key).map(|value| value.trim().to_owned()))
+        .filter(|value| !value.is_empty())
+}
+
+/// Decodes the image of an Xcursor file whose nominal size is closest to `size`.
+fn decode(data: &[u8], size: u32) -> Option<ImageHandle> {
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = data.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    };
+
+    if data.get(..4)? != XCURSOR_MAGIC {
+        return None;
+    }
+
+    let header_len = read_u32(4)? as usize;
+    let toc_len = read_u32(12)? as usize;
+
+    let position = (0..toc_len)
+        .filter_map(|n| {
+            let entry = header_len + n * 12;
+            if read_u32(entry)? != XCURSOR_IMAGE_TYPE {
+                return None;
+            }
+
+            Some((read_u32(entry + 4)?, read_u32(entry + 8)?))
+        })
+        .min_by_key(|(nominal, _)| nominal.abs_diff(size))
+        .map(|(_, position)| position as usize)?;
+
+    let width = read_u32(position + 16)?;
+    let height = read_u32(position + 20)?;
+    if width == 0 || height == 0 || width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE {
+        return None;
+    }
+
+    let pixels_start = position + 36;
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+
+    for n in 0..(width * height) as usize {
+        // Pixels are premultiplied ARGB.
+        let [b, g, r, a] = read_u32(pixels_start + n * 4)?.to_le_bytes();
+        let unmultiply = |c: u8| {
+            if a == 0 {
+                0
+            } else {
+                (u16::from(c) * 255 / u16::from(a)).min(255) as u8
+            }
+        };
+
+        rgba.extend_from_slice(&[unmultiply(r), unmultiply(g), unmultiply(b), a]);
+    }
+
+    Some(ImageHandle::from_pixels(width, height, rgba))
+}
+
+/// Makes the theme the default cursor theme, for toolkits which follow the
+/// freedesktop default theme, and for GNOME applications.
+pub async fn set_default(icon_dir: Option<PathBuf>, id: String) -> Result<(), String> {
+    let Some(icon_dir) = icon_dir else {
+        return Err("no user icon directory".to_owned());
+    };
+
+    write_default(&icon_dir.join("default"), &id).map_err(|why| why.to_string())?;
+
+    let status = tokio::process::Command::new("gsettings")
+        .args(["set", "org.gnome.desktop.interface", "cursor-theme", &id])
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gsettings exited with {status}"))
+    }
+}
+
+fn write_default(dir: &Path, id: &str) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(
+        dir.join("index.theme"),
+        format!("[Icon Theme]\nName=Default\nInherits={id}\n"),
+    )
+}
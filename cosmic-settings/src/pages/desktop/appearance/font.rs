@@ -0,0 +1,118 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+/// Size given to GNOME's font name when its current size cannot be read.
+const DEFAULT_SIZE: &str = "11";
+
+/// Which fonts a font setting applies to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FontKind {
+    Interface,
+    Monospace,
+}
+
+impl FontKind {
+    /// The fontconfig pattern matching fonts of this kind.
+    fn pattern(self) -> &'static str {
+        match self {
+            Self::Interface => ":",
+            Self::Monospace => ":spacing=mono",
+        }
+    }
+
+    /// The GNOME interface setting holding the font name.
+    fn gnome_key(self) -> &'static str {
+        match self {
+            Self::Interface => "font-name",
+            Self::Monospace => "monospace-font-name",
+        }
+    }
+}
+
+/// Lists the font families of a kind installed on the system, as known to fontconfig.
+pub async fn families(kind: FontKind) -> Vec<String> {
+    let output = match tokio::process::Command::new("fc-list")
+        .args([kind.pattern(), "family"])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => output.stdout,
+        Ok(output) => {
+            tracing::error!(status = ?output.status, "fc-list failed");
+            return Vec::new();
+        }
+        Err(why) => {
+            tracing::error!(?why, "failed to run fc-list");
+            return Vec::new();
+        }
+    };
+
+    // Families with localized names are listed as comma-separated aliases.
+    let mut families: Vec<String> = String::from_utf8_lossy(&output)
+        .lines()
+        .filter_map(|line| line.split(',').next())
+        .map(str::trim)
+        .filter(|family| !family.is_empty())
+        .map(String::from)
+        .collect();
+
+    families.sort_by_key(|family| family.to_lowercase());
+    families.dedup();
+    families
+}
+
+/// Sets a font family of GNOME/GTK applications, keeping its font size.
+pub async fn set_gnome_font(kind: FontKind, family: String) -> Result<(), String> {
+    let current = tokio::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", kind.gnome_key()])
+        .output()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    // The font name is quoted, and ends with its size, such as `'Cantarell 11'`.
+    let current = String::from_utf8_lossy(&current.stdout);
+    let size = current
+        .trim()
+        .trim_matches('\'')
+        .rsplit_once(' ')
+        .map(|(_, size)| size)
+        .filter(|size| size.parse::<f32>().is_ok())
+        .unwrap_or(DEFAULT_SIZE);
+
+    let status = tokio::process::Command::new("gsettings")
+        .args([
+            "set",
+            "org.gnome.desktop.interface",
+            kind.gnome_key(),
+            &format!("{family} {size}"),
+        ])
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gsettings exited with {status}"))
+    }
+}
+
+/// Sets the factor by which GNOME/GTK applications scale their text.
+pub async fn set_gnome_text_scaling(factor: f64) -> Result<(), String> {
+    let status = tokio::process::Command::new("gsettings")
+        .args([
+            "set",
+            "org.gnome.desktop.interface",
+            "text-scaling-factor",
+            &factor.to_string(),
+        ])
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gsettings exited with {status}"))
+    }
+}
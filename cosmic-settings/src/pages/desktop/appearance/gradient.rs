@@ -0,0 +1,60 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, LinSrgb, Srgb};
+use cosmic_settings_wallpaper as wallpaper;
+use serde::{Deserialize, Serialize};
+
+pub const MIN_STOPS: usize = 2;
+pub const MAX_STOPS: usize = 3;
+
+/// Direction of the gradient, in degrees.
+const ANGLE: f32 = 90.0;
+
+/// A gradient for the application background.
+///
+/// Themes only hold a solid background color, so the theme is given the average color
+/// of the gradient, which keeps contrast calculations meaningful.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BackgroundGradient {
+    pub stops: Vec<Srgb>,
+}
+
+impl BackgroundGradient {
+    /// A gradient from a color to a darker shade of it.
+    #[must_use]
+    pub fn from_color(color: Srgb) -> Self {
+        let mut shade = Hsv::from_color(color);
+        shade.value *= 0.8;
+
+        Self {
+            stops: vec![color, Srgb::from_color(shade)],
+        }
+    }
+
+    /// The average of the stops, blended in linear light.
+    #[must_use]
+    pub fn average(&self) -> Srgb {
+        let sum = self
+            .stops
+            .iter()
+            .map(|stop| stop.into_linear::<f32>())
+            .fold(LinSrgb::new(0.0, 0.0, 0.0), |sum, stop| sum + stop);
+
+        Srgb::from_linear(sum / self.stops.len().max(1) as f32)
+    }
+
+    /// The gradient, in the form drawn by wallpaper color previews.
+    #[must_use]
+    pub fn preview(&self) -> wallpaper::Color {
+        wallpaper::Color::Gradient(wallpaper::Gradient {
+            colors: self
+                .stops
+                .iter()
+                .map(|stop| [stop.red, stop.green, stop.blue])
+                .collect::<Vec<_>>()
+                .into(),
+            radius: ANGLE,
+        })
+    }
+}
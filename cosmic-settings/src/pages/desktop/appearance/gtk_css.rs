@@ -0,0 +1,101 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use cosmic::cosmic_theme::palette::Srgba;
+use cosmic::cosmic_theme::Theme;
+
+/// First line of the stylesheets written here, which tells them apart from the user's own.
+const HEADER: &str = "/* Generated by COSMIC Settings from the appearance theme. */";
+
+/// GTK versions whose stylesheets are written.
+const GTK_DIRS: [&str; 2] = ["gtk-3.0", "gtk-4.0"];
+
+/// A stylesheet defining the named colors of GTK and libadwaita from a theme.
+#[must_use]
+pub fn css(theme: &Theme) -> String {
+    let colors = [
+        ("accent_color", theme.accent.base),
+        ("accent_bg_color", theme.accent.base),
+        ("accent_fg_color", theme.accent.on),
+        ("window_bg_color", theme.background.base),
+        ("window_fg_color", theme.background.on),
+        ("view_bg_color", theme.primary.base),
+        ("view_fg_color", theme.primary.on),
+        ("headerbar_bg_color", theme.background.base),
+        ("headerbar_fg_color", theme.background.on),
+        ("card_bg_color", theme.primary.base),
+        ("card_fg_color", theme.primary.on),
+        ("popover_bg_color", theme.primary.base),
+        ("popover_fg_color", theme.primary.on),
+        ("dialog_bg_color", theme.primary.base),
+        ("dialog_fg_color", theme.primary.on),
+        ("sidebar_bg_color", theme.primary.base),
+        ("sidebar_fg_color", theme.primary.on),
+        ("theme_bg_color", theme.background.base),
+        ("theme_fg_color", theme.background.on),
+        ("theme_base_color", theme.primary.base),
+        ("theme_text_color", theme.primary.on),
+        ("theme_selected_bg_color", theme.accent.base),
+        ("theme_selected_fg_color", theme.accent.on),
+        ("borders", theme.background.component.base),
+        ("destructive_color", theme.destructive.base),
+        ("success_color", theme.success.base),
+        ("warning_color", theme.warning.base),
+    ];
+
+    let mut css = format!("{HEADER}\n");
+    for (name, color) in colors {
+        _ = writeln!(css, "@define-color {name} {};", rgba(color));
+    }
+
+    css
+}
+
+fn rgba(color: Srgba) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    format!(
+        "rgba({}, {}, {}, {})",
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue),
+        color.alpha.clamp(0.0, 1.0)
+    )
+}
+
+/// Writes the stylesheet to the `gtk.css` of GTK 3 and GTK 4.
+///
+/// A stylesheet previously written here is replaced. Any other existing stylesheet is
+/// first renamed to `gtk.css.bak`, replacing an older backup, so that the user's own
+/// styles can be restored.
+///
+/// # Errors
+///
+/// Returns an error if there is no config directory, or a stylesheet could not be written.
+pub fn write(css: &str) -> io::Result<()> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+
+    for dir in GTK_DIRS {
+        let dir = config_dir.join(dir);
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join("gtk.css");
+        if is_users_stylesheet(&path) {
+            std::fs::rename(&path, dir.join("gtk.css.bak"))?;
+        }
+
+        std::fs::write(&path, css)?;
+    }
+
+    Ok(())
+}
+
+/// Whether a stylesheet exists which was not written here.
+fn is_users_stylesheet(path: &Path) -> bool {
+    std::fs::read(path).is_ok_and(|contents| !contents.starts_with(HEADER.as_bytes()))
+}
@@ -0,0 +1,90 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Directories of a theme which hold styles for GTK 3 or GTK 4.
+const GTK_DIRS: [&str; 2] = ["gtk-4.0", "gtk-3.0"];
+
+/// Finds the installed GTK themes, sorted by name, along with the GTK theme which GNOME
+/// applications currently use.
+pub async fn fetch() -> (Vec<String>, Option<String>) {
+    let themes = tokio::task::spawn_blocking(find).await.unwrap_or_default();
+    (themes, active().await)
+}
+
+/// Sets the GTK theme of GNOME applications.
+pub async fn set(id: String) -> Result<(), String> {
+    let status = tokio::process::Command::new("gsettings")
+        .args(["set", "org.gnome.desktop.interface", "gtk-theme", &id])
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gsettings exited with {status}"))
+    }
+}
+
+async fn active() -> Option<String> {
+    let output = tokio::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "gtk-theme"])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    // GSettings prints strings quoted, as in `'Adwaita'`.
+    let id = String::from_utf8(output.stdout).ok()?;
+    let id = id.trim().trim_matches('\'');
+    (!id.is_empty()).then(|| id.to_owned())
+}
+
+fn find() -> Vec<String> {
+    let mut themes = BTreeSet::new();
+
+    for themes_dir in theme_dirs() {
+        let Ok(read_dir) = std::fs::read_dir(themes_dir) else {
+            continue;
+        };
+
+        for entry in read_dir.filter_map(Result::ok) {
+            let path = entry.path();
+            if !GTK_DIRS.iter().any(|dir| path.join(dir).is_dir()) {
+                continue;
+            }
+
+            if let Some(id) = entry.file_name().to_str() {
+                themes.insert(id.to_owned());
+            }
+        }
+    }
+
+    let mut themes: Vec<String> = themes.into_iter().collect();
+    themes.sort_by_key(|theme| theme.to_lowercase());
+    themes
+}
+
+/// The directories which GTK themes are installed to, including the legacy `~/.themes`.
+fn theme_dirs() -> Vec<PathBuf> {
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|value| !value.is_empty())
+        // Default from the XDG Base Directory Specification
+        .unwrap_or_else(|| "/usr/local/share/:/usr/share/".to_owned());
+
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|dir| dir.join(".local/share")));
+
+    std::env::split_paths(&data_dirs)
+        .chain(data_home)
+        .map(|dir| dir.join("themes"))
+        .chain(dirs::home_dir().map(|dir| dir.join(".themes")))
+        .collect()
+}
@@ -0,0 +1,73 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+/// Resolved paths of the icons previewed for each icon theme, which are slow to look up.
+///
+/// A path of `None` means that the theme has no icon of its own for that preview.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct IconCache {
+    themes: BTreeMap<String, Entry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Entry {
+    /// When the theme's `index.theme` was last modified.
+    modified: SystemTime,
+    paths: Vec<Option<PathBuf>>,
+}
+
+fn path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("cosmic-settings").join("icon-previews.ron"))
+}
+
+impl IconCache {
+    /// Reads the cache, which is empty if it does not exist or cannot be read.
+    #[must_use]
+    pub fn load() -> Self {
+        path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| ron::de::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// The cached paths of a theme, unless its `index.theme` was modified since, or an
+    /// icon was removed.
+    #[must_use]
+    pub fn get(&self, id: &str, modified: SystemTime) -> Option<&[Option<PathBuf>]> {
+        self.themes
+            .get(id)
+            .filter(|entry| entry.modified == modified)
+            .filter(|entry| entry.paths.iter().flatten().all(|path| path.exists()))
+            .map(|entry| entry.paths.as_slice())
+    }
+
+    pub fn insert(&mut self, id: String, modified: SystemTime, paths: Vec<Option<PathBuf>>) {
+        self.themes.insert(id, Entry { modified, paths });
+    }
+
+    /// Writes the cache, replacing the previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache could not be serialized or written.
+    pub fn save(&self) -> io::Result<()> {
+        let path =
+            path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory"))?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let contents = ron::ser::to_string(self)
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+
+        std::fs::write(path, contents)
+    }
+}
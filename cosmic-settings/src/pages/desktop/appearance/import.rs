@@ -0,0 +1,257 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_config::CosmicConfigEntry;
+use cosmic::cosmic_theme::ThemeBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::bundle::ThemeBundle;
+
+/// Scale factors offered when importing a theme.
+pub const SCALES: [f32; 6] = [0.5, 0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// Index of the unscaled factor in [`SCALES`].
+pub const DEFAULT_SCALE: usize = 2;
+
+/// A single exported theme, tagged with the version of the theme format it was made with.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VersionedTheme<T> {
+    pub version: u64,
+    pub theme: T,
+    /// ID of the icon theme to use with this theme.
+    #[serde(default)]
+    pub icon_theme: Option<String>,
+}
+
+impl<'a> VersionedTheme<&'a ThemeBuilder> {
+    /// Tags a theme and its icon theme with the current version of the theme format,
+    /// for exporting.
+    #[must_use]
+    pub fn current(theme: &'a ThemeBuilder, icon_theme: &str) -> Self {
+        Self {
+            version: ThemeBuilder::VERSION,
+            theme,
+            icon_theme: Some(icon_theme.to_owned()),
+        }
+    }
+}
+
+/// The version of a theme file, read without parsing the rest of the file.
+#[derive(Deserialize)]
+struct Header {
+    #[serde(default)]
+    version: Option<u64>,
+}
+
+/// Why a theme file could not be imported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The file is not a theme, or it could not be read.
+    Unreadable,
+    /// The theme was made for a newer version of the theme format.
+    Newer,
+    /// The theme was made for an older, incompatible version of the theme format.
+    Older,
+    /// The clipboard holds no text to import.
+    EmptyClipboard,
+}
+
+impl Error {
+    /// A localized description of the error.
+    #[must_use]
+    pub fn message(self) -> String {
+        match self {
+            Self::Unreadable => fl!("import-theme", "failed"),
+            Self::Newer => fl!("import-theme", "newer"),
+            Self::Older => fl!("import-theme", "older"),
+            Self::EmptyClipboard => fl!("import-theme", "empty-clipboard"),
+        }
+    }
+}
+
+/// A theme read from an imported file, which has yet to be applied.
+#[derive(Clone, Debug)]
+pub enum ImportedTheme {
+    /// A theme for the active mode, with the ID of its icon theme.
+    Single(Box<ThemeBuilder>, Option<String>),
+    Bundle(Box<ThemeBundle>),
+}
+
+impl ImportedTheme {
+    /// Parses a theme file, which holds either a single theme or a light and dark bundle.
+    ///
+    /// Exported themes are versioned, and only themes of the current version are accepted.
+    /// A file without a version is not rejected as incompatible: it is either a bundle or
+    /// a theme exported before versioning, and is accepted if it parses as one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not a theme, or was made for a different version.
+    pub fn parse(contents: &str) -> Result<Self, Error> {
+        if let Ok(Header {
+            version: Some(version),
+        }) = ron::de::from_str::<Header>(contents)
+        {
+            return match version.cmp(&ThemeBuilder::VERSION) {
+                std::cmp::Ordering::Greater => Err(Error::Newer),
+                std::cmp::Ordering::Less => Err(Error::Older),
+                std::cmp::Ordering::Equal => {
+                    ron::de::from_str::<VersionedTheme<ThemeBuilder>>(contents)
+                        .map(|versioned| {
+                            Self::Single(Box::new(versioned.theme), versioned.icon_theme)
+                        })
+                        .map_err(|_| Error::Unreadable)
+                }
+            };
+        }
+
+        // A bundle must be tried first, since its fields are required.
+        if let Ok(bundle) = ron::de::from_str::<ThemeBundle>(contents) {
+            return Ok(Self::Bundle(Box::new(bundle)));
+        }
+
+        ron::de::from_str::<ThemeBuilder>(contents)
+            .map(|builder| Self::Single(Box::new(builder), None))
+            .map_err(|_| Error::Unreadable)
+    }
+
+    /// The imported theme with its spacing-related sizes multiplied by `factor`.
+    #[must_use]
+    pub fn scaled(&self, factor: f32) -> Self {
+        let mut theme = self.clone();
+
+        match &mut theme {
+            Self::Single(builder, _) => scale(builder, factor),
+            Self::Bundle(bundle) => {
+                scale(&mut bundle.light, factor);
+                scale(&mut bundle.dark, factor);
+            }
+        }
+
+        theme
+    }
+
+    /// ID of the icon theme which the imported theme was shared with.
+    #[must_use]
+    pub fn icon_theme(&self) -> Option<&str> {
+        match self {
+            Self::Single(_, icon_theme) => icon_theme.as_deref(),
+            Self::Bundle(bundle) => bundle.icon_theme.as_deref(),
+        }
+    }
+
+    /// The theme builder that applies to the given color mode.
+    #[must_use]
+    pub fn builder(&self, is_dark: bool) -> ThemeBuilder {
+        match self {
+            Self::Single(builder, _) => (**builder).clone(),
+            Self::Bundle(bundle) => {
+                let (light, dark) = (**bundle).clone().into_builders();
+                if is_dark {
+                    dark
+                } else {
+                    light
+                }
+            }
+        }
+    }
+}
+
+/// Multiplies the gaps, active hint, corner radii, and spacing of a theme builder.
+fn scale(builder: &mut ThemeBuilder, factor: f32) {
+    let scale_u32 = |value: u32| (value as f32 * factor).round() as u32;
+
+    builder.gaps = (scale_u32(builder.gaps.0), scale_u32(builder.gaps.1));
+    builder.active_hint = scale_u32(builder.active_hint);
+
+    let radii = &mut builder.corner_radii;
+    for radius in [
+        &mut radii.radius_0,
+        &mut radii.radius_xs,
+        &mut radii.radius_s,
+        &mut radii.radius_m,
+        &mut radii.radius_l,
+        &mut radii.radius_xl,
+    ] {
+        *radius = radius.map(|r| r * factor);
+    }
+
+    let spacing = &mut builder.spacing;
+    for space in [
+        &mut spacing.space_none,
+        &mut spacing.space_xxxs,
+        &mut spacing.space_xxs,
+        &mut spacing.space_xs,
+        &mut spacing.space_s,
+        &mut spacing.space_m,
+        &mut spacing.space_l,
+        &mut spacing.space_xl,
+        &mut spacing.space_xxl,
+        &mut spacing.space_xxxl,
+    ] {
+        *space = (f32::from(*space) * factor).round() as u16;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versioned(version: u64) -> String {
+        let theme = VersionedTheme {
+            version,
+            theme: ThemeBuilder::dark(),
+            icon_theme: None,
+        };
+        ron::ser::to_string(&theme).unwrap()
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let contents = versioned(ThemeBuilder::VERSION + 1);
+        assert_eq!(ImportedTheme::parse(&contents).unwrap_err(), Error::Newer);
+    }
+
+    #[test]
+    fn rejects_older_version() {
+        let Some(version) = ThemeBuilder::VERSION.checked_sub(1) else {
+            return;
+        };
+
+        let contents = versioned(version);
+        assert_eq!(ImportedTheme::parse(&contents).unwrap_err(), Error::Older);
+    }
+
+    #[test]
+    fn accepts_matching_version() {
+        let builder = ThemeBuilder::dark();
+        let contents = ron::ser::to_string(&VersionedTheme::current(&builder, "Pop")).unwrap();
+
+        match ImportedTheme::parse(&contents) {
+            Ok(ImportedTheme::Single(theme, icon_theme)) => {
+                assert_eq!(*theme, builder);
+                assert_eq!(icon_theme.as_deref(), Some("Pop"));
+            }
+            other => panic!("expected a single theme, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_missing_version_as_legacy_theme() {
+        let builder = ThemeBuilder::light();
+        let contents = ron::ser::to_string(&builder).unwrap();
+
+        match ImportedTheme::parse(&contents) {
+            Ok(ImportedTheme::Single(theme, None)) => assert_eq!(*theme, builder),
+            other => panic!("expected a legacy theme, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_unreadable_file() {
+        assert_eq!(
+            ImportedTheme::parse("not a theme").unwrap_err(),
+            Error::Unreadable
+        );
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::sync::Arc;
+
+use button::StyleSheet as ButtonStyleSheet;
+use cosmic::cosmic_theme::palette::Srgba;
+use cosmic::cosmic_theme::ThemeBuilder;
+use cosmic::iced_core::{Alignment, Background, Border, Color, Length};
+use cosmic::widget::{self, button, container, horizontal_space, icon, row, text};
+use cosmic::{Apply, Element};
+
+/// Widgets drawn with the theme being edited, before it is written.
+///
+/// Iced themes the whole window at once, so each widget here is given styles taken
+/// from the edited theme rather than from the session theme.
+pub struct LivePreview {
+    builder: ThemeBuilder,
+    theme: cosmic::Theme,
+}
+
+impl LivePreview {
+    #[must_use]
+    pub fn new(builder: &ThemeBuilder) -> Self {
+        Self {
+            builder: builder.clone(),
+            theme: cosmic::Theme::custom(Arc::new(builder.clone().build())),
+        }
+    }
+
+    /// Rebuilds the previewed theme if the builder has changed since it was last built.
+    pub fn update(&mut self, builder: &ThemeBuilder) {
+        if self.builder != *builder {
+            *self = Self::new(builder);
+        }
+    }
+
+    pub fn view<'a, Message: Clone + 'static>(&self) -> Element<'a, Message> {
+        let cosmic = self.theme.cosmic();
+        let spacing = &cosmic.spacing;
+
+        let buttons = row::with_capacity(2)
+            .push(preview_button(
+                &self.theme,
+                fl!("live-preview", "button"),
+                cosmic::theme::Button::Suggested,
+            ))
+            .push(preview_button(
+                &self.theme,
+                fl!("live-preview", "secondary-button"),
+                cosmic::theme::Button::Standard,
+            ))
+            .spacing(spacing.space_xs);
+
+        let text_field = text::body(fl!("live-preview", "text-field"))
+            .apply(container)
+            .width(Length::Fill)
+            .padding([spacing.space_xxs, spacing.space_s])
+            .style(text_field_style(&self.theme));
+
+        let toggle = row::with_capacity(3)
+            .push(text::body(fl!("live-preview", "toggle")))
+            .push(horizontal_space(Length::Fill))
+            .push(toggler(&self.theme))
+            .align_items(Alignment::Center);
+
+        let list_row = row::with_capacity(3)
+            .push(icon::from_name("folder-symbolic").size(16).icon())
+            .push(text::body(fl!("live-preview", "list-row")))
+            .push(horizontal_space(Length::Fill))
+            .push(icon::from_name("go-next-symbolic").size(16).icon())
+            .spacing(spacing.space_xs)
+            .align_items(Alignment::Center)
+            .apply(container)
+            .width(Length::Fill)
+            .padding([spacing.space_xs, spacing.space_s])
+            .style(layer_style(&self.theme, Layer::Component));
+
+        widget::column::with_capacity(4)
+            .push(buttons)
+            .push(text_field)
+            .push(toggle)
+            .push(list_row)
+            .spacing(spacing.space_s)
+            .apply(container)
+            .width(Length::Fill)
+            .padding(spacing.space_s)
+            .style(layer_style(&self.theme, Layer::Primary))
+            .apply(container)
+            .padding(spacing.space_s)
+            .style(layer_style(&self.theme, Layer::Background))
+            .into()
+    }
+}
+
+/// A button with the styles of the previewed theme.
+///
+/// Preview buttons do nothing, so their disabled style is the active one.
+fn preview_button<'a, Message: Clone + 'static>(
+    preview: &cosmic::Theme,
+    label: String,
+    style: cosmic::theme::Button,
+) -> Element<'a, Message> {
+    let style = Arc::new(style);
+    let (active, disabled, hovered, pressed) = (
+        (preview.clone(), style.clone()),
+        (preview.clone(), style.clone()),
+        (preview.clone(), style.clone()),
+        (preview.clone(), style),
+    );
+
+    button::standard(label)
+        .style(cosmic::theme::Button::Custom {
+            active: Box::new(move |focused, _| active.0.active(focused, false, &active.1)),
+            disabled: Box::new(move |_| disabled.0.active(false, false, &disabled.1)),
+            hovered: Box::new(move |focused, _| hovered.0.hovered(focused, false, &hovered.1)),
+            pressed: Box::new(move |focused, _| pressed.0.pressed(focused, false, &pressed.1)),
+        })
+        .into()
+}
+
+/// An enabled toggle, in the accent color of the previewed theme.
+fn toggler<'a, Message: 'static>(preview: &cosmic::Theme) -> Element<'a, Message> {
+    let cosmic = preview.cosmic();
+    let knob_color = color(cosmic.accent.on);
+
+    horizontal_space(Length::Fixed(12.0))
+        .apply(container)
+        .style(cosmic::theme::Container::custom(move |_| {
+            container::Appearance {
+                background: Some(Background::Color(knob_color)),
+                border: Border {
+                    radius: 6.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }))
+        .height(Length::Fixed(12.0))
+        .apply(container)
+        .width(Length::Fixed(40.0))
+        .padding(4)
+        .align_x(cosmic::iced_core::alignment::Horizontal::Right)
+        .style(accent_style(preview))
+        .into()
+}
+
+#[derive(Clone, Copy)]
+enum Layer {
+    Background,
+    Primary,
+    Component,
+}
+
+fn layer_style(preview: &cosmic::Theme, layer: Layer) -> cosmic::theme::Container {
+    let cosmic = preview.cosmic();
+    let (base, on, radius) = match layer {
+        Layer::Background => (
+            cosmic.background.base,
+            cosmic.background.on,
+            cosmic.corner_radii.radius_m,
+        ),
+        Layer::Primary => (
+            cosmic.primary.base,
+            cosmic.primary.on,
+            cosmic.corner_radii.radius_s,
+        ),
+        Layer::Component => (
+            cosmic.primary.component.base,
+            cosmic.primary.component.on,
+            cosmic.corner_radii.radius_s,
+        ),
+    };
+    let (base, on) = (color(base), color(on));
+
+    cosmic::theme::Container::custom(move |_| container::Appearance {
+        icon_color: Some(on),
+        text_color: Some(on),
+        background: Some(Background::Color(base)),
+        border: Border {
+            radius: radius.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn text_field_style(preview: &cosmic::Theme) -> cosmic::theme::Container {
+    let cosmic = preview.cosmic();
+    let background = color(cosmic.background.base);
+    let text_color = color(cosmic.background.on);
+    let border = color(cosmic.accent.base);
+    let radius = cosmic.corner_radii.radius_s;
+
+    cosmic::theme::Container::custom(move |_| container::Appearance {
+        text_color: Some(text_color),
+        background: Some(Background::Color(background)),
+        border: Border {
+            color: border,
+            width: 1.0,
+            radius: radius.into(),
+        },
+        ..Default::default()
+    })
+}
+
+fn accent_style(preview: &cosmic::Theme) -> cosmic::theme::Container {
+    let cosmic = preview.cosmic();
+    let background = color(cosmic.accent.base);
+
+    cosmic::theme::Container::custom(move |_| container::Appearance {
+        background: Some(Background::Color(background)),
+        border: Border {
+            radius: 10.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn color(srgba: Srgba) -> Color {
+    Color::from_rgba(srgba.red, srgba.green, srgba.blue, srgba.alpha)
+}
@@ -0,0 +1,8040 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod bundle;
+mod color_format;
+mod config;
+mod contrast;
+mod cursor;
+mod font;
+mod gradient;
+mod gtk_css;
+mod gtk_theme;
+mod icon_cache;
+mod import;
+mod live_preview;
+mod profile;
+mod qr;
+mod random;
+mod sample;
+mod schedule;
+mod seed;
+mod snapshot;
+mod terminal;
+mod thumbnail;
+mod vision;
+mod wallpaper_accent;
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+
+use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
+use cosmic::config::CosmicTk;
+use cosmic::cosmic_config::{Config, ConfigSet, CosmicConfigEntry};
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, Srgb, Srgba, WithAlpha};
+use cosmic::cosmic_theme::{
+    CornerRadii, CosmicPalette, Theme, ThemeBuilder, ThemeMode, DARK_THEME_BUILDER_ID,
+    LIGHT_THEME_BUILDER_ID,
+};
+use cosmic::iced_core::keyboard::key::Named;
+use cosmic::iced_core::keyboard::{Key, Modifiers};
+use cosmic::iced_core::widget::operation::focusable;
+use cosmic::iced_core::{alignment, Background, Color, Length};
+use cosmic::iced_widget::scrollable;
+use cosmic::prelude::CollectionWidget;
+use cosmic::widget::icon::{self, from_name, icon};
+use cosmic::widget::{
+    button, color_picker::ColorPickerUpdate, container, dropdown, flex_row, horizontal_space,
+    radio, row, settings, spin_button, text, ColorPickerModel,
+};
+use cosmic::Apply;
+use cosmic::{command, Command, Element};
+use cosmic_panel_config::CosmicPanelConfig;
+use cosmic_settings_page::Section;
+use cosmic_settings_page::{self as page, section};
+use cosmic_settings_wallpaper as wallpaper;
+use i18n_embed::DesktopLanguageRequester;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use slotmap::SlotMap;
+
+use crate::app;
+
+use self::bundle::ThemeBundle;
+use self::color_format::{ColorFormat, ColorInputMode};
+use self::config::Config as PageConfig;
+use self::contrast::{contrast_ratio, ContrastStandard};
+use self::cursor::CursorTheme;
+use self::font::FontKind;
+use self::gradient::BackgroundGradient;
+use self::icon_cache::IconCache;
+use self::import::{ImportedTheme, VersionedTheme};
+use self::live_preview::LivePreview;
+use self::profile::LookProfile;
+use self::sample::SampleKind;
+use self::schedule::{Schedule, TimeOfDay};
+use self::seed::SeedPalette;
+use self::snapshot::{Snapshot, SnapshotId};
+use self::terminal::TerminalFormat;
+use self::thumbnail::ThemeThumbnail;
+use self::vision::ColorVisionMode;
+use super::wallpaper::widgets::color_image;
+
+const ICON_PREV_N: usize = 6;
+const ICON_PREV_ROW: usize = 3;
+/// Sizes at which preview icons are looked up, in order. Every preview size is included,
+/// with smaller sizes last since scaling icons down looks better than scaling them up.
+const ICON_TRY_SIZES: [u16; 4] = [32, 48, 64, 24];
+const ICON_THUMB_SIZE: u16 = 32;
+/// Sizes which icon theme previews can be shown at.
+const ICON_PREVIEW_SIZES: [u16; 3] = [24, 32, 48];
+const ICON_NAME_TRUNC: usize = 20;
+/// Icon theme which every theme falls back to, which is not selectable by itself.
+const FALLBACK_ICON_THEME: &str = "hicolor";
+/// Icon themes found before they are shown while scanning.
+const ICON_SCAN_BATCH: usize = 8;
+const ROUNDNESS_ANIMATION: Duration = Duration::from_millis(200);
+/// Most theme changes which can be undone.
+const HISTORY_LIMIT: usize = 50;
+/// The largest active window hint, in pixels.
+const MAX_ACTIVE_HINT: u32 = 16;
+/// The largest gap around or between tiled windows, in pixels.
+const MAX_GAP: u32 = 64;
+/// Size of the squares of the checkerboard drawn behind translucent colors.
+const CHECKER_SIZE: u16 = 6;
+/// How long the theme must stay unchanged before it is written.
+const THEME_WRITE_DEBOUNCE: Duration = Duration::from_millis(150);
+/// How long the text scaling must stay unchanged before it is written.
+const TEXT_SCALING_DEBOUNCE: Duration = Duration::from_millis(300);
+/// Size of body text at a text scaling factor of 1, in points.
+const BODY_TEXT_POINTS: f64 = 10.5;
+/// Code shown in the monospace font, for judging its ligatures and zero.
+const MONOSPACE_SAMPLE: &str = "fn main() {} // 0O";
+pub const ANIMATION_FRAME: Duration = Duration::from_millis(16);
+type IconThemes = Vec<IconTheme>;
+type IconHandles = Vec<PreviewHandles>;
+type PreviewHandles = [PreviewIcon; ICON_PREV_N];
+/// Preview paths and handles of each icon theme from the last scan, keyed by theme ID,
+/// along with when the theme's `index.theme` was modified.
+type IconPreviewMemo = HashMap<String, (SystemTime, Vec<Option<PathBuf>>, PreviewHandles)>;
+/// Sends messages to the page from outside of its commands.
+type PageSender = tokio::sync::mpsc::Sender<crate::pages::Message>;
+
+crate::cache_dynamic_lazy! {
+    static HEX: String = fl!("hex");
+    static RGB: String = fl!("rgb");
+    static RESET_TO_DEFAULT: String = fl!("reset-to-default");
+    static ICON_THEME: String = fl!("icon-theme");
+    static ICON_THEME_DESC: String = fl!("icon-theme", "desc");
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ContextView {
+    AccentWindowHint,
+    ApplicationBackground,
+    AutoSwitchSchedule,
+    ConfirmOverwrite,
+    ConfirmRestoreDefaults,
+    ContainerBackground,
+    ControlComponent,
+    CustomAccent,
+    CustomRoundness,
+    DayAccent,
+    Experimental,
+    ImportPreview,
+    InterfaceText,
+    NightAccent,
+    SampleApp(SampleKind),
+    SeedColor,
+    SelectionColor,
+    ThemeQr,
+}
+
+impl ContextView {
+    /// The kind of color picker shown, under which its recent colors are remembered.
+    ///
+    /// The accents of both color modes share their recent colors.
+    fn recent_colors_kind(self) -> Option<&'static str> {
+        match self {
+            Self::AccentWindowHint => Some("window-hint"),
+            Self::ApplicationBackground => Some("application-background"),
+            Self::ContainerBackground => Some("container-background"),
+            Self::ControlComponent => Some("control-tint"),
+            Self::CustomAccent | Self::DayAccent | Self::NightAccent => Some("accent"),
+            Self::InterfaceText => Some("text-tint"),
+            Self::SelectionColor => Some("selection"),
+            _ => None,
+        }
+    }
+
+    /// The message updating the color picker shown.
+    fn color_picker_message(self) -> Option<fn(ColorPickerUpdate) -> Message> {
+        match self {
+            Self::AccentWindowHint => Some(Message::AccentWindowHint),
+            Self::ApplicationBackground => Some(Message::ApplicationBackground),
+            Self::ContainerBackground => Some(Message::ContainerBackground),
+            Self::ControlComponent => Some(Message::ControlComponent),
+            Self::CustomAccent => Some(Message::CustomAccent),
+            Self::DayAccent => Some(Message::DayAccent),
+            Self::NightAccent => Some(Message::NightAccent),
+            Self::InterfaceText => Some(Message::InterfaceText),
+            Self::SelectionColor => Some(Message::SelectionColor),
+            _ => None,
+        }
+    }
+}
+
+/// An accent offered in the accent row.
+struct AccentSwatch {
+    name: String,
+    color: Srgba,
+    /// Position among the saved accents, if the accent was saved by the user.
+    saved: Option<usize>,
+}
+
+/// The layout settings copied to the other color mode by a sync.
+#[derive(Debug, Default)]
+struct SyncOutcome {
+    /// Whether any setting differed between the modes.
+    synced: bool,
+    /// Settings which could not be written to the other mode.
+    failed: usize,
+}
+
+impl SyncOutcome {
+    /// Number of layout settings which are synced.
+    const FIELDS: usize = 3;
+
+    /// Records a differing setting, given the number of its writes that failed.
+    fn record(&mut self, errors: usize) {
+        self.synced = true;
+        if errors > 0 {
+            self.failed += 1;
+        }
+    }
+}
+
+/// A summary of the appearance settings to attach to bug reports.
+#[derive(Serialize)]
+struct Diagnostics<'a> {
+    theme_mode: &'a ThemeMode,
+    theme_builder: &'a ThemeBuilder,
+    icon_theme: &'a str,
+    toolkit: &'a CosmicTk,
+    environment: BTreeMap<&'static str, Option<String>>,
+}
+
+impl Diagnostics<'_> {
+    /// Environment variables which affect how themes are applied.
+    const ENVIRONMENT: [&'static str; 6] = [
+        "GTK_THEME",
+        "XDG_CURRENT_DESKTOP",
+        "XDG_SESSION_TYPE",
+        "XDG_DATA_HOME",
+        "XDG_DATA_DIRS",
+        "XDG_CONFIG_HOME",
+    ];
+}
+
+/// Interpolates the corner radii of the page's preview after a roundness change.
+struct RoundnessAnimation {
+    from: CornerRadii,
+    to: CornerRadii,
+    start: Instant,
+}
+
+impl RoundnessAnimation {
+    /// Progress of the animation, from 0.0 to 1.0.
+    fn progress(&self) -> f32 {
+        (self.start.elapsed().as_secs_f32() / ROUNDNESS_ANIMATION.as_secs_f32()).min(1.0)
+    }
+
+    fn radii(&self) -> CornerRadii {
+        let t = self.progress();
+        let lerp =
+            |from: [f32; 4], to: [f32; 4]| std::array::from_fn(|i| from[i] + (to[i] - from[i]) * t);
+
+        CornerRadii {
+            radius_0: lerp(self.from.radius_0, self.to.radius_0),
+            radius_xs: lerp(self.from.radius_xs, self.to.radius_xs),
+            radius_s: lerp(self.from.radius_s, self.to.radius_s),
+            radius_m: lerp(self.from.radius_m, self.to.radius_m),
+            radius_l: lerp(self.from.radius_l, self.to.radius_l),
+            radius_xl: lerp(self.from.radius_xl, self.to.radius_xl),
+        }
+    }
+}
+
+/// Theme colors which can be individually locked while randomizing.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ColorRole {
+    Accent,
+    ApplicationBackground,
+    ContainerBackground,
+    InterfaceText,
+    ControlComponent,
+    WindowHint,
+}
+
+/// Window management sizes which can be typed in.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum SizeField {
+    ActiveHint,
+    Gap,
+    OuterGap,
+}
+
+impl SizeField {
+    fn max(self) -> u32 {
+        match self {
+            Self::ActiveHint => MAX_ACTIVE_HINT,
+            Self::Gap | Self::OuterGap => MAX_GAP,
+        }
+    }
+
+    fn value_mut(self, builder: &mut ThemeBuilder) -> &mut u32 {
+        match self {
+            Self::ActiveHint => &mut builder.active_hint,
+            Self::Gap => &mut builder.gaps.1,
+            Self::OuterGap => &mut builder.gaps.0,
+        }
+    }
+}
+
+/// The settings reverted by a reset.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ResetScope {
+    Colors,
+    /// Roundness, gaps, and the active hint.
+    Layout,
+    #[default]
+    Everything,
+}
+
+impl ResetScope {
+    pub const ALL: [Self; 3] = [Self::Colors, Self::Layout, Self::Everything];
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Colors => fl!("reset-to-default", "colors"),
+            Self::Layout => fl!("reset-to-default", "layout"),
+            Self::Everything => fl!("reset-to-default", "everything"),
+        }
+    }
+
+    /// The theme with the settings of this scope taken from the default theme.
+    fn apply(self, current: &ThemeBuilder, default: ThemeBuilder) -> ThemeBuilder {
+        let (mut builder, layout) = match self {
+            Self::Colors => (default, current),
+            Self::Layout => (current.clone(), &default),
+            Self::Everything => return default,
+        };
+
+        builder.corner_radii = layout.corner_radii;
+        builder.gaps = layout.gaps;
+        builder.active_hint = layout.active_hint;
+        builder
+    }
+
+    /// Whether resetting this scope would change the theme.
+    fn differs(self, current: &ThemeBuilder, default: &ThemeBuilder) -> bool {
+        self.apply(current, default.clone()) != *current
+    }
+}
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct IconTheme {
+    // COSMIC uses the file name of the folder containing the theme
+    id: String,
+    // GTK uses the name of the theme as specified in its index file
+    name: String,
+    // Localized description of the theme from its index file
+    comment: Option<String>,
+    // When the theme's directory was last modified
+    installed: Option<SystemTime>,
+    // When the theme's index file was last modified
+    index_modified: Option<SystemTime>,
+    // Directories from which icons of the theme are considered valid
+    inherits: Vec<String>,
+}
+
+/// Order in which icon themes are displayed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum IconThemeSort {
+    #[default]
+    Name,
+    /// By the name of the theme's directory.
+    Id,
+    RecentlyUsed,
+    Newest,
+}
+
+impl IconThemeSort {
+    pub const ALL: [Self; 4] = [Self::Name, Self::Id, Self::RecentlyUsed, Self::Newest];
+}
+
+pub struct Page {
+    /// Scopes which would change the theme if reset.
+    resettable: Vec<ResetScope>,
+    /// The scope reset by the reset button.
+    reset_scope: ResetScope,
+    reset_scopes: Vec<String>,
+    no_custom_window_hint: bool,
+    context_view: Option<ContextView>,
+    custom_accent: ColorPickerModel,
+    accent_window_hint: ColorPickerModel,
+    application_background: ColorPickerModel,
+    container_background: ColorPickerModel,
+    interface_text: ColorPickerModel,
+    /// Percentage of the text tint's saturation to keep, previewed until applied.
+    text_tint_intensity: f32,
+    /// Text scaling factor shown by the slider, which is written once it settles.
+    text_scaling: f64,
+    /// Incremented on each change of the text scaling, so that only the last is written.
+    text_scaling_generation: u64,
+    /// Whether edits to a corner radius apply to all four corners.
+    link_corners: bool,
+    /// Sizes which are being typed in, before they are submitted.
+    size_drafts: BTreeMap<SizeField, String>,
+    control_component: ColorPickerModel,
+    selection_color: ColorPickerModel,
+    /// Accents of the light and dark themes, while they are edited side by side.
+    day_accent: ColorPickerModel,
+    night_accent: ColorPickerModel,
+    roundness: Roundness,
+    /// Whether the panel or dock floats, so that its corners follow the roundness.
+    floating_panel: bool,
+    roundness_animation: Option<RoundnessAnimation>,
+    /// Whether the user has been told that layout changes sync to the other mode.
+    sync_notified: bool,
+    /// Whether a failure to sync was shown during this visit.
+    sync_failure_notified: bool,
+    locked_colors: BTreeSet<ColorRole>,
+    /// Accent candidates sampled from a wallpaper, kept until the wallpaper changes.
+    wallpaper_accents: Option<(PathBuf, Vec<Srgb>)>,
+    seed_color: ColorPickerModel,
+    seed_palette: Option<SeedPalette>,
+
+    icon_theme_active: Option<usize>,
+    icon_themes: IconThemes,
+    icon_handles: IconHandles,
+    cursor_themes: Vec<CursorTheme>,
+    cursor_theme_active: Option<usize>,
+    /// Installed GTK themes, shared with the GTK theme dropdown's selection handler.
+    gtk_themes: Arc<[String]>,
+    /// The GTK theme which GNOME applications use.
+    gtk_theme_active: Option<String>,
+    /// Installed font families, shared with the font dropdown's selection handler.
+    font_families: Arc<[String]>,
+    monospace_families: Arc<[String]>,
+    icon_theme_sorts: Vec<String>,
+    icon_symbolic_preview: bool,
+    /// Whether icon theme previews mark icons which the theme does not provide.
+    icon_diagnostics: bool,
+    /// Size of the icons previewed for each icon theme.
+    icon_preview_size: u16,
+    /// Opacity of the active window hint, by which it is blended into the background.
+    window_hint_opacity: f32,
+    /// Opacities of the application and container backgrounds.
+    application_opacity: f32,
+    container_opacity: f32,
+    /// Symbolic preview handles of the active icon theme, by theme ID.
+    symbolic_handles: Option<(String, PreviewHandles)>,
+
+    theme_mode: ThemeMode,
+    theme_mode_config: Option<Config>,
+    theme_builder: ThemeBuilder,
+    theme_builder_needs_update: bool,
+    /// A color picker whose color was just applied, to remember among its recent colors.
+    applied_color_picker: Option<ContextView>,
+    /// The color being edited in the color picker shown, once it has been changed.
+    picker_color: Option<Srgb>,
+    /// Format of colors copied from color pickers.
+    copy_format: ColorFormat,
+    copy_formats: Vec<String>,
+    /// Components in which colors are typed into color pickers.
+    color_input_mode: ColorInputMode,
+    color_input_modes: Vec<String>,
+    /// Typed color components which have not been submitted yet.
+    color_input_drafts: Option<[String; 3]>,
+    accent_scrollable: cosmic::widget::Id,
+    /// Widgets drawn with the theme being edited.
+    live_preview: LivePreview,
+    /// Whether changes to the theme are waiting to be written.
+    theme_write_pending: bool,
+    /// Incremented on each delayed write, so that only the last of rapid changes is written.
+    theme_write_generation: u64,
+    theme_builder_config: Option<Config>,
+
+    auto_switch_descs: [Cow<'static, str>; 4],
+
+    tk: CosmicTk,
+    tk_config: Option<Config>,
+
+    day_time: bool,
+    /// Themes before each change, from oldest to newest, for undoing changes.
+    undo_history: Vec<ThemeBuilder>,
+    /// Themes before each undo, from oldest to newest.
+    redo_history: Vec<ThemeBuilder>,
+    /// Custom times to switch the theme mode at, instead of sunrise and sunset.
+    auto_switch_schedule: Option<Schedule>,
+    /// Whether location services have reported whether it is day.
+    location_available: bool,
+    latitude_input: String,
+    longitude_input: String,
+
+    /// The last valid theme builder applied to the session while previewing.
+    ///
+    /// When set, changes are only applied to the session, and nothing is
+    /// written to disk until the preview is committed.
+    preview: Option<ThemeBuilder>,
+
+    /// Set once writing the theme config has failed, such as on immutable systems.
+    read_only: bool,
+    /// Whether a file is being dragged over the window, which may be dropped to import it.
+    file_hovering: bool,
+
+    /// An imported theme waiting for its scale to be chosen.
+    pending_import: Option<ImportedTheme>,
+    import_scale: usize,
+    import_scales: Vec<String>,
+
+    /// An export waiting for confirmation to replace an existing file, and whether it is
+    /// a theme file to remember as recent.
+    pending_export: Option<(PathBuf, String, bool)>,
+    /// File names of the recently imported or exported theme files.
+    recent_theme_files: Vec<String>,
+
+    /// QR code of the current theme, for sharing with another device.
+    theme_qr: Option<cosmic::iced_runtime::core::image::Handle>,
+
+    /// Stored snapshots, from newest to oldest, with the time they were taken.
+    snapshots: Vec<(SnapshotId, String)>,
+    /// Thumbnails of the stored snapshots, generated when they are first shown.
+    snapshot_thumbnails: BTreeMap<SnapshotId, ThemeThumbnail>,
+    /// Names of the stored look profiles.
+    look_profiles: Vec<String>,
+    /// Name entered for saving the current look as a profile.
+    look_profile_name: String,
+    /// Case-insensitive text which icon theme names are filtered by.
+    icon_theme_filter: String,
+    /// Names of the icon themes, in the order they are listed.
+    icon_theme_names: Vec<String>,
+    /// Desktop ID of the application entered for an icon theme override.
+    icon_override_app: String,
+    /// ID of the icon theme chosen for an icon theme override.
+    icon_override_theme: Option<String>,
+    /// Whether icon themes are being searched for, so that scans do not overlap.
+    icon_refresh_in_flight: bool,
+    /// An imported icon theme which was not found, to apply if the next scan finds it.
+    pending_icon_theme: Option<String>,
+    /// Sends icon themes to the page as they are found.
+    icon_scan_sender: Option<PageSender>,
+    /// Icon previews of the last scan, which are reused for unchanged themes.
+    icon_preview_memo: Arc<Mutex<IconPreviewMemo>>,
+
+    config: PageConfig,
+    contrast_standards: Vec<String>,
+    /// Color vision simulated in accent previews.
+    color_vision: ColorVisionMode,
+    color_visions: Vec<String>,
+    /// Format of exported terminal palettes.
+    terminal_format: TerminalFormat,
+    terminal_formats: Vec<String>,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        load_theme_mode().into()
+    }
+}
+
+impl
+    From<(
+        Option<Config>,
+        ThemeMode,
+        Option<Config>,
+        ThemeBuilder,
+        Option<Config>,
+        CosmicTk,
+    )> for Page
+{
+    fn from(
+        configs: (
+            Option<Config>,
+            ThemeMode,
+            Option<Config>,
+            ThemeBuilder,
+            Option<Config>,
+            CosmicTk,
+        ),
+    ) -> Self {
+        Self::with_config(PageConfig::new(), panel_is_floating(), configs)
+    }
+}
+
+impl Page {
+    /// Creates the page from settings which were already loaded.
+    fn with_config(
+        config: PageConfig,
+        floating_panel: bool,
+        (theme_mode_config, theme_mode, theme_builder_config, theme_builder, tk_config, tk): (
+            Option<Config>,
+            ThemeMode,
+            Option<Config>,
+            ThemeBuilder,
+            Option<Config>,
+            CosmicTk,
+        ),
+    ) -> Self {
+        let (latitude_input, longitude_input) = config
+            .manual_location()
+            .map_or_else(Default::default, |(latitude, longitude)| {
+                (latitude.to_string(), longitude.to_string())
+            });
+
+        let auto_switch_schedule = theme_mode_config.as_ref().and_then(Schedule::load);
+        let empty_model = || ColorPickerModel::new(&*HEX, &*RGB, None, None);
+
+        let mut page = Self {
+            resettable: Vec::new(),
+            reset_scope: ResetScope::default(),
+            reset_scopes: ResetScope::ALL.map(ResetScope::title).to_vec(),
+            theme_builder_needs_update: false,
+            applied_color_picker: None,
+            picker_color: None,
+            copy_format: ColorFormat::default(),
+            copy_formats: ColorFormat::ALL.map(ColorFormat::title).to_vec(),
+            color_input_mode: ColorInputMode::default(),
+            color_input_modes: ColorInputMode::ALL.map(ColorInputMode::title).to_vec(),
+            color_input_drafts: None,
+            accent_scrollable: cosmic::widget::Id::unique(),
+            live_preview: LivePreview::new(&theme_builder),
+            theme_write_pending: false,
+            theme_write_generation: 0,
+            context_view: None,
+            roundness: Roundness::Round,
+            floating_panel,
+            roundness_animation: None,
+            sync_notified: false,
+            sync_failure_notified: false,
+            locked_colors: BTreeSet::new(),
+            wallpaper_accents: None,
+            seed_color: empty_model(),
+            seed_palette: None,
+            custom_accent: empty_model(),
+            application_background: empty_model(),
+            container_background: empty_model(),
+            interface_text: empty_model(),
+            text_tint_intensity: 100.0,
+            text_scaling: config.text_scaling_factor(),
+            text_scaling_generation: 0,
+            link_corners: true,
+            size_drafts: BTreeMap::new(),
+            control_component: empty_model(),
+            selection_color: empty_model(),
+            day_accent: empty_model(),
+            night_accent: empty_model(),
+            accent_window_hint: empty_model(),
+            no_custom_window_hint: false,
+            icon_theme_active: None,
+            icon_themes: Vec::new(),
+            icon_handles: Vec::new(),
+            cursor_themes: Vec::new(),
+            cursor_theme_active: None,
+            gtk_themes: Arc::from([]),
+            gtk_theme_active: None,
+            font_families: Arc::from([]),
+            monospace_families: Arc::from([]),
+            icon_symbolic_preview: false,
+            icon_diagnostics: false,
+            icon_preview_size: config.icon_preview_size().unwrap_or(ICON_THUMB_SIZE),
+            window_hint_opacity: config.window_hint_opacity(),
+            application_opacity: 1.0,
+            container_opacity: 1.0,
+            symbolic_handles: None,
+            icon_theme_sorts: vec![
+                fl!("icon-theme-sort", "name"),
+                fl!("icon-theme-sort", "id"),
+                fl!("icon-theme-sort", "recently-used"),
+                fl!("icon-theme-sort", "newest"),
+            ],
+            theme_mode_config,
+            theme_builder_config,
+            theme_mode,
+            theme_builder: ThemeBuilder::default(),
+            tk_config,
+            tk,
+            day_time: true,
+            undo_history: Vec::new(),
+            redo_history: Vec::new(),
+            auto_switch_schedule,
+            location_available: false,
+            latitude_input,
+            longitude_input,
+            preview: None,
+            read_only: false,
+            file_hovering: false,
+            pending_import: None,
+            import_scale: import::DEFAULT_SCALE,
+            import_scales: import::SCALES
+                .iter()
+                .map(|scale| format!("{}%", (scale * 100.0).round()))
+                .collect(),
+            pending_export: None,
+            recent_theme_files: Vec::new(),
+            theme_qr: None,
+            snapshots: Vec::new(),
+            snapshot_thumbnails: BTreeMap::new(),
+            look_profiles: Vec::new(),
+            look_profile_name: String::new(),
+            icon_theme_names: Vec::new(),
+            icon_override_app: String::new(),
+            icon_override_theme: None,
+            icon_theme_filter: String::new(),
+            icon_refresh_in_flight: false,
+            pending_icon_theme: None,
+            icon_scan_sender: None,
+            icon_preview_memo: Arc::default(),
+            auto_switch_descs: [
+                fl!("auto-switch", "sunrise").into(),
+                fl!("auto-switch", "sunset").into(),
+                fl!("auto-switch", "next-sunrise").into(),
+                fl!("auto-switch", "next-sunset").into(),
+            ],
+            config,
+            contrast_standards: vec![
+                fl!("contrast-standard", "aa"),
+                fl!("contrast-standard", "aaa"),
+            ],
+            color_vision: ColorVisionMode::default(),
+            color_visions: ColorVisionMode::ALL.map(ColorVisionMode::title).to_vec(),
+            terminal_format: TerminalFormat::default(),
+            terminal_formats: TerminalFormat::ALL.map(TerminalFormat::title).to_vec(),
+        };
+
+        page.set_theme_builder(theme_builder);
+        page
+    }
+}
+
+impl From<(Option<Config>, ThemeMode)> for Page {
+    fn from((theme_mode_config, theme_mode): (Option<Config>, ThemeMode)) -> Self {
+        let (theme_builder_config, theme_builder) = load_theme_builder(theme_mode.is_dark);
+        let (tk_config, tk) = load_toolkit();
+        (
+            theme_mode_config,
+            theme_mode,
+            theme_builder_config,
+            theme_builder,
+            tk_config,
+            tk,
+        )
+            .into()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    AccentWindowHint(ColorPickerUpdate),
+    AddGradientStop,
+    ApplicationBackground(ColorPickerUpdate),
+    ApplicationGradient(bool),
+    AnimationTick,
+    ApplySeedPalette,
+    ApplyThemeGlobal(bool),
+    Autoswitch(bool),
+    AutoSwitchCustom(bool),
+    AutoSwitchDarkTime(TimeOfDay),
+    AutoSwitchLightTime(TimeOfDay),
+    AutoSwitchSchedule,
+    CancelImport,
+    ConfirmImport,
+    ContainerBackground(ColorPickerUpdate),
+    ControlComponent(ColorPickerUpdate),
+    ContrastStandard(ContrastStandard),
+    CopyColorsFromOtherMode,
+    ColorVision(ColorVisionMode),
+    LinkCorners(bool),
+    CommitPreview,
+    CornerRadius(RadiusSize, Corner, spin_button::Message),
+    CustomAccent(ColorPickerUpdate),
+    CustomRoundness,
+    DarkMode(bool),
+    DecorationAccentOnly(bool),
+    DiagnosticsFile(Arc<SelectedFiles>),
+    Entered((IconThemes, IconHandles)),
+    ExperimentalContextDrawer,
+    ExportDiagnostics,
+    ExportError,
+    FileChooserClosed,
+    ExportFile(Arc<SelectedFiles>),
+    ExportBundleFile(Arc<SelectedFiles>),
+    ExportSuccess(Option<PathBuf>),
+    ExportToClipboard,
+    TextTintIntensity(f32),
+    ApplyTextTintIntensity,
+    ConfirmOverwrite,
+    CancelOverwrite,
+    StartRestoreAllDefaults,
+    CancelRestoreDefaults,
+    RestoreAllDefaults,
+    OverwriteWithoutConfirm(bool),
+    GapSize(spin_button::Message),
+    OuterGapSize(spin_button::Message),
+    SizeInput(SizeField, String),
+    SizeSubmit(SizeField),
+    GenerateFromSeed(cosmic::iced::Color),
+    GradientStop(usize),
+    IconTheme(usize),
+    IconThemeGnomeResult(Result<(), String>),
+    ColorSchemeGnomeResult(Result<(), String>),
+    IconThemeFilter(String),
+    CursorTheme(usize),
+    CursorThemeResult(Result<(), String>),
+    CursorThemesScanned((Vec<CursorTheme>, Option<String>)),
+    GtkTheme(String),
+    GtkThemeResult(Result<(), String>),
+    GtkThemesScanned((Vec<String>, Option<String>)),
+    FontFamilies(FontKind, Vec<String>),
+    GnomeFontResult(Result<(), String>),
+    InterfaceFont(String),
+    ManualLatitude(String),
+    ManualLongitude(String),
+    MonospaceFont(String),
+    TextScaling(f64),
+    FlushThemeWrite(u64),
+    TextScalingSettled(u64),
+    IconThemeSort(IconThemeSort),
+    IconSymbolicPreview(bool),
+    IconDiagnostics(bool),
+    IconPreviewSize(u16),
+    IconSymbolicHandles(String, Box<PreviewHandles>),
+    IconThemeImportFile(Arc<SelectedFiles>),
+    IconScanExcludeDir(Arc<SelectedFiles>),
+    IconThemesScanned((IconThemes, IconHandles)),
+    IconThemesFound((IconThemes, IconHandles)),
+    RefreshIconThemes,
+    ImportError(import::Error),
+    ImportBundleSuccess(Box<ThemeBundle>),
+    ImportFile(Arc<SelectedFiles>),
+    ImportRecent(usize),
+    FileHovered(bool),
+    FileDropped(PathBuf),
+    ImportIconTheme(Option<String>),
+    ImportLoaded(ImportedTheme, Option<PathBuf>),
+    ImportScale(usize),
+    ImportSuccess(Box<ThemeBuilder>),
+    InterfaceText(ColorPickerUpdate),
+    Left,
+    PaletteAccent(cosmic::iced::Color),
+    PaletteAccentAdd,
+    MoveAccentFocus(isize),
+    AccentFocusFrom(cosmic::widget::Id, isize),
+    PaletteAccentRemove(usize),
+    PreviewMode(bool),
+    RandomizeColors,
+    RandomizeAccent,
+    AccentFromWallpaper,
+    WallpaperAccents(PathBuf, Vec<Srgb>),
+    RemoveGradientStop,
+    RemoveIconScanExclude(PathBuf),
+    IconOverrideApp(String),
+    IconOverrideTheme(usize),
+    AddIconOverride(String, usize),
+    RemoveIconOverride(String),
+    ResetScope(ResetScope),
+    SelectResetScope(ResetScope),
+    HighContrast(bool),
+    RestoreSnapshot(SnapshotId),
+    LookProfileName(String),
+    SaveLookProfile(String),
+    LoadLookProfile(String),
+    DeleteLookProfile(String),
+    SnapshotThumbnail(SnapshotId, Option<ThemeThumbnail>),
+    Roundness(Roundness),
+    SeedColor(ColorPickerUpdate),
+    SelectionColor(ColorPickerUpdate),
+    SeparateModeAccents(bool),
+    SyncShape(bool),
+    DayAccent(ColorPickerUpdate),
+    NightAccent(ColorPickerUpdate),
+    ShowThemeQr,
+    PreviewSampleApp(SampleKind),
+    StartExport,
+    StartExportBundle,
+    ExportGtkCss,
+    RecentColor(Srgb),
+    CopyFormat(ColorFormat),
+    CopyColor,
+    ColorInputMode(ColorInputMode),
+    ColorInput(usize, String),
+    ColorInputSubmit,
+    ClearRecentColors,
+    ExportTerminalPalette(TerminalFormat),
+    TerminalFormat(TerminalFormat),
+    TerminalPaletteFile(Arc<SelectedFiles>),
+    StartIconScanExclude,
+    StartIconThemeImport,
+    StartImport,
+    ImportFromClipboard,
+    TakeSnapshot,
+    Undo,
+    Redo,
+    ToggleColorLock(ColorRole),
+    ToggleDarkMode,
+    UseDefaultWindowHint(bool),
+    WindowHintOpacity(f32),
+    ApplicationOpacity(f32),
+    ContainerOpacity(f32),
+    WindowHintSize(spin_button::Message),
+    Daytime(bool),
+}
+
+impl Message {
+    /// Whether the message changes the theme config.
+    fn modifies_theme(&self) -> bool {
+        matches!(
+            self,
+            Self::AccentWindowHint(_)
+                | Self::AddGradientStop
+                | Self::ApplicationBackground(_)
+                | Self::ApplicationGradient(_)
+                | Self::ApplicationOpacity(_)
+                | Self::ApplySeedPalette
+                | Self::ApplyTextTintIntensity
+                | Self::ColorInputSubmit
+                | Self::CommitPreview
+                | Self::ConfirmImport
+                | Self::ContainerBackground(_)
+                | Self::ContainerOpacity(_)
+                | Self::ControlComponent(_)
+                | Self::CopyColorsFromOtherMode
+                | Self::CornerRadius(..)
+                | Self::CustomAccent(_)
+                | Self::DayAccent(_)
+                | Self::DecorationAccentOnly(_)
+                | Self::GapSize(_)
+                | Self::GradientStop(_)
+                | Self::HighContrast(_)
+                | Self::ImportBundleSuccess(_)
+                | Self::ImportSuccess(_)
+                | Self::InterfaceText(_)
+                | Self::LoadLookProfile(_)
+                | Self::NightAccent(_)
+                | Self::OuterGapSize(_)
+                | Self::PaletteAccent(_)
+                | Self::RandomizeAccent
+                | Self::RandomizeColors
+                | Self::RecentColor(_)
+                | Self::Redo
+                | Self::ResetScope(_)
+                | Self::RestoreAllDefaults
+                | Self::RestoreSnapshot(_)
+                | Self::Roundness(_)
+                | Self::SelectionColor(_)
+                | Self::SizeSubmit(_)
+                | Self::Undo
+                | Self::UseDefaultWindowHint(_)
+                | Self::WindowHintOpacity(_)
+                | Self::WindowHintSize(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Roundness {
+    Round,
+    SlightlyRound,
+    Square,
+    /// Radii which differ from every preset.
+    Custom(CornerRadii),
+}
+
+impl Roundness {
+    const PRESETS: [Self; 3] = [Self::Round, Self::SlightlyRound, Self::Square];
+}
+
+/// A corner of a radius, in the order in which `CornerRadii` stores them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+impl Corner {
+    const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomRight,
+        Self::BottomLeft,
+    ];
+
+    fn title(self) -> String {
+        match self {
+            Self::TopLeft => fl!("style", "top-left"),
+            Self::TopRight => fl!("style", "top-right"),
+            Self::BottomRight => fl!("style", "bottom-right"),
+            Self::BottomLeft => fl!("style", "bottom-left"),
+        }
+    }
+}
+
+/// A corner radius of the theme which may be edited individually.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadiusSize {
+    ExtraSmall,
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl RadiusSize {
+    const ALL: [Self; 5] = [
+        Self::ExtraSmall,
+        Self::Small,
+        Self::Medium,
+        Self::Large,
+        Self::ExtraLarge,
+    ];
+
+    fn title(self) -> String {
+        match self {
+            Self::ExtraSmall => fl!("style", "radius-xs"),
+            Self::Small => fl!("style", "radius-s"),
+            Self::Medium => fl!("style", "radius-m"),
+            Self::Large => fl!("style", "radius-l"),
+            Self::ExtraLarge => fl!("style", "radius-xl"),
+        }
+    }
+
+    fn radius(self, radii: &CornerRadii) -> [f32; 4] {
+        match self {
+            Self::ExtraSmall => radii.radius_xs,
+            Self::Small => radii.radius_s,
+            Self::Medium => radii.radius_m,
+            Self::Large => radii.radius_l,
+            Self::ExtraLarge => radii.radius_xl,
+        }
+    }
+
+    fn radius_mut(self, radii: &mut CornerRadii) -> &mut [f32; 4] {
+        match self {
+            Self::ExtraSmall => &mut radii.radius_xs,
+            Self::Small => &mut radii.radius_s,
+            Self::Medium => &mut radii.radius_m,
+            Self::Large => &mut radii.radius_l,
+            Self::ExtraLarge => &mut radii.radius_xl,
+        }
+    }
+}
+
+impl From<Roundness> for CornerRadii {
+    fn from(value: Roundness) -> Self {
+        match value {
+            Roundness::Round => CornerRadii {
+                radius_0: [0.0; 4],
+                radius_xs: [4.0; 4],
+                radius_s: [8.0; 4],
+                radius_m: [16.0; 4],
+                radius_l: [32.0; 4],
+                radius_xl: [160.0; 4],
+            },
+            Roundness::SlightlyRound => CornerRadii {
+                radius_0: [0.0; 4],
+                radius_xs: [2.0; 4],
+                radius_s: [8.0; 4],
+                radius_m: [8.0; 4],
+                radius_l: [8.0; 4],
+                radius_xl: [8.0; 4],
+            },
+            Roundness::Square => CornerRadii {
+                radius_0: [0.0; 4],
+                radius_xs: [2.0; 4],
+                radius_s: [2.0; 4],
+                radius_m: [2.0; 4],
+                radius_l: [2.0; 4],
+                radius_xl: [2.0; 4],
+            },
+            Roundness::Custom(radii) => radii,
+        }
+    }
+}
+
+impl From<CornerRadii> for Roundness {
+    fn from(value: CornerRadii) -> Self {
+        let radii = |radii: &CornerRadii| {
+            [
+                radii.radius_0,
+                radii.radius_xs,
+                radii.radius_s,
+                radii.radius_m,
+                radii.radius_l,
+                radii.radius_xl,
+            ]
+        };
+
+        let matches = |preset: CornerRadii| {
+            radii(&preset)
+                .iter()
+                .flatten()
+                .zip(radii(&value).iter().flatten())
+                .all(|(a, b)| (a - b).abs() < 0.01)
+        };
+
+        Self::PRESETS
+            .into_iter()
+            .find(|&preset| matches(preset.into()))
+            .unwrap_or(Self::Custom(value))
+    }
+}
+
+impl Page {
+    /// Copies the active hint, gaps, and corner radii of this mode to the other mode. Colors,
+    /// including the accent, stay with each mode so day and night can have their own accents.
+    fn sync_changes(&self) -> Result<SyncOutcome, cosmic::cosmic_config::Error> {
+        let (other_builder_config, other_theme_config) = if self.theme_mode.is_dark {
+            (ThemeBuilder::light_config()?, Theme::light_config()?)
+        } else {
+            (ThemeBuilder::dark_config()?, Theme::dark_config()?)
+        };
+
+        let mut theme_builder = match ThemeBuilder::get_entry(&other_builder_config) {
+            Ok(t) => t,
+            Err((errs, t)) => {
+                for err in errs {
+                    tracing::error!(?err, "Error loading theme builder");
+                }
+                t
+            }
+        };
+        let mut theme = match Theme::get_entry(&other_theme_config) {
+            Ok(t) => t,
+            Err((errs, t)) => {
+                for err in errs {
+                    tracing::error!(?err, "Error loading theme");
+                }
+                t
+            }
+        };
+        let mut outcome = SyncOutcome::default();
+
+        if theme_builder.active_hint != self.theme_builder.active_hint {
+            outcome.record(
+                [
+                    theme_builder
+                        .set_active_hint(&other_builder_config, self.theme_builder.active_hint),
+                    theme.set_active_hint(&other_theme_config, self.theme_builder.active_hint),
+                ]
+                .into_iter()
+                .filter_map(Result::err)
+                .inspect(|err| tracing::error!(?err, "Error setting active hint"))
+                .count(),
+            );
+        }
+        if theme_builder.gaps != self.theme_builder.gaps {
+            outcome.record(
+                [
+                    theme_builder.set_gaps(&other_builder_config, self.theme_builder.gaps),
+                    theme.set_gaps(&other_theme_config, self.theme_builder.gaps),
+                ]
+                .into_iter()
+                .filter_map(Result::err)
+                .inspect(|err| tracing::error!(?err, "Error setting gaps"))
+                .count(),
+            );
+        }
+        if theme_builder.corner_radii != self.theme_builder.corner_radii {
+            outcome.record(
+                [
+                    theme_builder
+                        .set_corner_radii(&other_builder_config, self.theme_builder.corner_radii),
+                    theme.set_corner_radii(&other_theme_config, self.theme_builder.corner_radii),
+                ]
+                .into_iter()
+                .filter_map(Result::err)
+                .inspect(|err| tracing::error!(?err, "Error setting corner radii"))
+                .count(),
+            );
+        }
+
+        Ok(outcome)
+    }
+
+    fn color_picker_context_view(
+        &self,
+        description: Option<Cow<'static, str>>,
+        reset: Cow<'static, str>,
+        on_update: fn(ColorPickerUpdate) -> Message,
+        model: impl Fn(&Self) -> &ColorPickerModel,
+    ) -> Element<'_, crate::pages::Message> {
+        cosmic::widget::column()
+            .push_maybe(description.map(|description| text(description).width(Length::Fill)))
+            .push(
+                model(self)
+                    .builder(on_update)
+                    .reset_label(reset)
+                    .height(Length::Fixed(158.0))
+                    .build(
+                        fl!("recent-colors"),
+                        fl!("copy-to-clipboard"),
+                        fl!("copied-to-clipboard"),
+                    )
+                    .apply(container)
+                    .width(Length::Fixed(248.0))
+                    .align_x(alignment::Horizontal::Center)
+                    .apply(container)
+                    .width(Length::Fill)
+                    .align_x(alignment::Horizontal::Center),
+            )
+            .push(
+                row::with_capacity(2)
+                    .push(dropdown(
+                        &self.copy_formats,
+                        ColorFormat::ALL
+                            .iter()
+                            .position(|format| *format == self.copy_format),
+                        |i| Message::CopyFormat(ColorFormat::ALL[i]),
+                    ))
+                    .push(button::standard(fl!("copy-to-clipboard")).on_press(Message::CopyColor))
+                    .spacing(self.theme_builder.spacing.space_xs),
+            )
+            .push_maybe(self.color_input_view())
+            .push_maybe(self.recent_colors_view())
+            .padding(self.theme_builder.spacing.space_l)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .spacing(self.theme_builder.spacing.space_m)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    /// Fields for typing the hue, saturation, and lightness or value of the color being
+    /// edited, which replace it once submitted.
+    fn color_input_view(&self) -> Option<Element<'_, Message>> {
+        self.context_view?.color_picker_message()?;
+
+        let mode = self.color_input_mode;
+        let spacing = &self.theme_builder.spacing;
+        let components = match &self.color_input_drafts {
+            Some(drafts) => drafts.clone(),
+            None => self
+                .edited_color()
+                .map(|color| mode.components(color))
+                .unwrap_or_default(),
+        };
+
+        let fields = mode.labels().into_iter().zip(components).enumerate().fold(
+            row::with_capacity(3).spacing(spacing.space_xs),
+            |row, (n, (label, component))| {
+                row.push(
+                    cosmic::widget::column::with_capacity(2)
+                        .push(text::caption(label))
+                        .push(
+                            cosmic::widget::text_input("", component)
+                                .on_input(move |input| Message::ColorInput(n, input))
+                                .on_submit(Message::ColorInputSubmit),
+                        )
+                        .spacing(spacing.space_xxxs)
+                        .width(Length::Fill),
+                )
+            },
+        );
+
+        let input = cosmic::widget::column::with_capacity(2)
+            .push(dropdown(
+                &self.color_input_modes,
+                ColorInputMode::ALL.iter().position(|m| *m == mode),
+                |i| Message::ColorInputMode(ColorInputMode::ALL[i]),
+            ))
+            .push(fields)
+            .spacing(spacing.space_xs)
+            .width(Length::Fixed(248.0));
+
+        Some(input.into())
+    }
+
+    /// The remembered recent colors of the color picker shown, which apply when pressed.
+    fn recent_colors_view(&self) -> Option<Element<'_, Message>> {
+        let kind = self.context_view?.recent_colors_kind()?;
+        let colors = self.config.recent_colors(kind);
+
+        if colors.is_empty() {
+            return None;
+        }
+
+        let spacing = &self.theme_builder.spacing;
+        let swatches = colors.iter().fold(
+            row::with_capacity(colors.len()).spacing(spacing.space_xxs),
+            |row, color| {
+                row.push(color_button(
+                    Some(Message::RecentColor(*color)),
+                    Color::from(*color),
+                    false,
+                    24,
+                    24,
+                ))
+            },
+        );
+
+        let header = row::with_capacity(3)
+            .push(text::heading(fl!("recent-colors", "saved")))
+            .push(horizontal_space(Length::Fill))
+            .push(button::text(fl!("recent-colors", "clear")).on_press(Message::ClearRecentColors))
+            .align_items(cosmic::iced_core::Alignment::Center);
+
+        let recent = cosmic::widget::column::with_capacity(2)
+            .push(header)
+            .push(swatches)
+            .spacing(spacing.space_xs)
+            .width(Length::Fixed(248.0));
+
+        Some(recent.into())
+    }
+
+    /// A seed color picker, followed by a preview of the theme colors derived from it.
+    fn seed_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let picker = self.color_picker_context_view(
+            Some(fl!("seed-color", "desc").into()),
+            RESET_TO_DEFAULT.as_str().into(),
+            Message::SeedColor,
+            |this| &this.seed_color,
+        );
+
+        let Some(palette) = self.seed_palette else {
+            return picker;
+        };
+
+        let swatches = palette.colors().into_iter().fold(
+            row::with_capacity(5).spacing(self.theme_builder.spacing.space_xxs),
+            |row, color| row.push(color_button(None, Color::from(color), false, 48, 48)),
+        );
+
+        let preview = cosmic::widget::column::with_capacity(2)
+            .push(swatches)
+            .push(button::suggested(fl!("seed-color", "apply")).on_press(Message::ApplySeedPalette))
+            .spacing(self.theme_builder.spacing.space_s)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance);
+
+        cosmic::widget::column::with_capacity(2)
+            .push(picker)
+            .push(preview)
+            .into()
+    }
+
+    /// The custom accent picker, with a button to save the color to the user's palette.
+    fn custom_accent_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let picker = self.color_picker_context_view(
+            None,
+            RESET_TO_DEFAULT.as_str().into(),
+            Message::CustomAccent,
+            |this| &this.custom_accent,
+        );
+
+        let unsaved = self
+            .custom_accent
+            .get_applied_color()
+            .map(Srgb::from)
+            .filter(|color| !self.config.saved_accents().contains(color));
+
+        let add = button::standard(fl!("accent-palette", "add"))
+            .on_press_maybe(unsaved.map(|_| Message::PaletteAccentAdd))
+            .apply(container)
+            .width(Length::Fill)
+            .align_x(alignment::Horizontal::Center)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance);
+
+        cosmic::widget::column::with_capacity(2)
+            .push(picker)
+            .push(add)
+            .into()
+    }
+
+    /// Scale options for a pending import, with the sizes that result from the chosen scale,
+    /// and the settings which would change if it were applied.
+    fn import_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let builder = self.pending_import.as_ref().map(|theme| {
+            theme
+                .scaled(import::SCALES[self.import_scale])
+                .builder(self.theme_mode.is_dark)
+        });
+
+        let summary = builder.as_ref().map(|builder| {
+            text::caption(fl!(
+                "import-scale",
+                "summary",
+                gaps = builder.gaps.1,
+                hint = builder.active_hint,
+                radius = builder.corner_radii.radius_m[0].round() as u32
+            ))
+        });
+
+        let changes = builder
+            .as_ref()
+            .map(|builder| import_changes(&self.theme_builder, builder));
+
+        cosmic::widget::column()
+            .push(text(fl!("import-scale", "desc")).width(Length::Fill))
+            .push(settings::view_section("").add(
+                settings::item::builder(fl!("import-scale", "scale")).control(dropdown(
+                    &self.import_scales,
+                    Some(self.import_scale),
+                    Message::ImportScale,
+                )),
+            ))
+            .push_maybe(summary)
+            .push_maybe(changes)
+            .push(
+                row::with_capacity(2)
+                    .push(button::standard(fl!("cancel")).on_press(Message::CancelImport))
+                    .push(button::suggested(fl!("import")).on_press(Message::ConfirmImport))
+                    .spacing(self.theme_builder.spacing.space_xxs),
+            )
+            .padding(self.theme_builder.spacing.space_l)
+            .spacing(self.theme_builder.spacing.space_m)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    /// Asks whether an export should replace the existing file at its path.
+    fn confirm_overwrite_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let name = self
+            .pending_export
+            .as_ref()
+            .and_then(|(path, ..)| path.file_name())
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+
+        cosmic::widget::column()
+            .push(text(fl!("confirm-overwrite", "desc", file = name)).width(Length::Fill))
+            .push(settings::view_section("").add(
+                settings::item::builder(fl!("confirm-overwrite", "always")).toggler(
+                    self.config.overwrite_without_confirm(),
+                    Message::OverwriteWithoutConfirm,
+                ),
+            ))
+            .push(
+                row::with_capacity(2)
+                    .push(button::standard(fl!("cancel")).on_press(Message::CancelOverwrite))
+                    .push(
+                        button::suggested(fl!("confirm-overwrite", "replace"))
+                            .on_press(Message::ConfirmOverwrite),
+                    )
+                    .spacing(self.theme_builder.spacing.space_xxs),
+            )
+            .padding(self.theme_builder.spacing.space_l)
+            .spacing(self.theme_builder.spacing.space_m)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    fn confirm_restore_defaults_context_view(&self) -> Element<'_, crate::pages::Message> {
+        cosmic::widget::column()
+            .push(text(fl!("restore-defaults", "desc")).width(Length::Fill))
+            .push(
+                row::with_capacity(2)
+                    .push(button::standard(fl!("cancel")).on_press(Message::CancelRestoreDefaults))
+                    .push(
+                        button::destructive(fl!("restore-defaults", "confirm"))
+                            .on_press(Message::RestoreAllDefaults),
+                    )
+                    .spacing(self.theme_builder.spacing.space_xxs),
+            )
+            .padding(self.theme_builder.spacing.space_l)
+            .spacing(self.theme_builder.spacing.space_m)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    /// A sample application styled with the live theme, and a choice of sample.
+    fn sample_app_context_view(&self, kind: SampleKind) -> Element<'_, crate::pages::Message> {
+        let choices = SampleKind::ALL.into_iter().fold(
+            row::with_capacity(SampleKind::ALL.len()).spacing(self.theme_builder.spacing.space_xxs),
+            |row, choice| {
+                row.push(
+                    button::text(choice.title())
+                        .selected(choice == kind)
+                        .on_press(Message::PreviewSampleApp(choice)),
+                )
+            },
+        );
+
+        cosmic::widget::column::with_capacity(3)
+            .push(text(fl!("sample-app", "desc")))
+            .push(choices)
+            .push(sample::view(kind))
+            .spacing(self.theme_builder.spacing.space_m)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    /// The QR code of the current theme, to be scanned by another device.
+    fn theme_qr_context_view(&self) -> Element<'_, crate::pages::Message> {
+        cosmic::widget::column()
+            .push(text(fl!("theme-qr", "desc")).width(Length::Fill))
+            .push_maybe(self.theme_qr.clone().map(|handle| {
+                cosmic::iced::widget::image(handle)
+                    .width(Length::Fixed(256.0))
+                    .height(Length::Fixed(256.0))
+            }))
+            .padding(self.theme_builder.spacing.space_l)
+            .spacing(self.theme_builder.spacing.space_m)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// A selection color picker, followed by a sample of selected text.
+    fn selection_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let picker = self.color_picker_context_view(
+            None,
+            RESET_TO_DEFAULT.as_str().into(),
+            Message::SelectionColor,
+            |this| &this.selection_color,
+        );
+
+        let Some(color) = self
+            .selection_color
+            .get_applied_color()
+            .or_else(|| self.theme_builder.accent.map(Color::from))
+        else {
+            return picker;
+        };
+
+        let text_color = Color::from(selection_text_color(Srgb::from(color)));
+
+        let sample = text::body(fl!("selection-color", "sample"))
+            .apply(container)
+            .padding([2, 4])
+            .style(cosmic::theme::Container::custom(move |_| {
+                cosmic::widget::container::Appearance {
+                    text_color: Some(text_color),
+                    background: Some(Background::Color(color)),
+                    ..Default::default()
+                }
+            }))
+            .apply(container)
+            .width(Length::Fill)
+            .align_x(alignment::Horizontal::Center);
+
+        cosmic::widget::column::with_capacity(2)
+            .push(picker)
+            .push(sample)
+            .into()
+    }
+
+    /// A color control with a toggle which excludes the color from randomization.
+    fn lockable_color_control<'a>(
+        &'a self,
+        role: ColorRole,
+        model: &'a ColorPickerModel,
+        on_update: fn(ColorPickerUpdate) -> Message,
+    ) -> Element<'a, Message> {
+        row::with_capacity(2)
+            .push(color_lock_button(role, self.locked_colors.contains(&role)))
+            .push(color_picker_control(model, on_update))
+            .spacing(self.theme_builder.spacing.space_xxs)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .into()
+    }
+
+    /// Generates symbolic preview handles for the active icon theme, if they are needed.
+    fn load_symbolic_handles(&self) -> Command<app::Message> {
+        if !self.icon_symbolic_preview {
+            return Command::none();
+        }
+
+        let Some(theme) = self
+            .icon_theme_active
+            .and_then(|id| self.icon_themes.get(id))
+        else {
+            return Command::none();
+        };
+
+        if self
+            .symbolic_handles
+            .as_ref()
+            .is_some_and(|(id, _)| *id == theme.id)
+        {
+            return Command::none();
+        }
+
+        let id = theme.id.clone();
+        let inherits = theme.inherits.clone();
+
+        Command::perform(
+            async move {
+                let theme = id.clone();
+                // `icon::from_name` may perform blocking I/O
+                match tokio::task::spawn_blocking(move || preview_handles(theme, inherits, true))
+                    .await
+                {
+                    Ok(handles) => Message::IconSymbolicHandles(id, Box::new(handles)),
+                    Err(why) => {
+                        tracing::error!(?why, "failed to generate symbolic icon previews");
+                        Message::IconSymbolicPreview(false)
+                    }
+                }
+            },
+            |message| crate::Message::PageMessage(crate::pages::Message::Appearance(message)),
+        )
+    }
+
+    /// Orders the icon themes by the configured sort, and locates the active icon theme.
+    fn sort_icon_themes(&mut self) {
+        let mut themes: Vec<_> = std::mem::take(&mut self.icon_themes)
+            .into_iter()
+            .zip(std::mem::take(&mut self.icon_handles))
+            .collect();
+
+        match self.config.icon_theme_sort() {
+            IconThemeSort::Name => {
+                themes.sort_by(|(a, _), (b, _)| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            }
+            IconThemeSort::Id => {
+                themes.sort_by(|(a, _), (b, _)| a.id.cmp(&b.id));
+            }
+            IconThemeSort::RecentlyUsed => {
+                themes.sort_by_key(|(theme, _)| {
+                    std::cmp::Reverse(self.config.icon_theme_last_used(&theme.id))
+                });
+            }
+            IconThemeSort::Newest => {
+                themes.sort_by_key(|(theme, _)| std::cmp::Reverse(theme.installed));
+            }
+        }
+
+        (self.icon_themes, self.icon_handles) = themes.into_iter().unzip();
+        self.icon_theme_names = self
+            .icon_themes
+            .iter()
+            .map(|theme| theme.name.clone())
+            .collect();
+        self.icon_theme_active = self
+            .icon_themes
+            .iter()
+            .position(|theme| theme.id == self.tk.icon_theme);
+    }
+
+    /// Searches for icon themes again, such as after the excluded directories change.
+    fn rescan_icon_themes(&mut self) -> Command<app::Message> {
+        if self.icon_refresh_in_flight {
+            return Command::none();
+        }
+
+        self.icon_refresh_in_flight = true;
+        let excludes = self.config.icon_scan_excludes().to_vec();
+        let sender = self.icon_scan_sender.clone();
+        let memo = self.icon_preview_memo.clone();
+        Command::perform(fetch_icon_themes(excludes, sender, memo), |themes| {
+            crate::Message::PageMessage(crate::pages::Message::Appearance(
+                Message::IconThemesScanned(themes),
+            ))
+        })
+    }
+
+    /// Whether the page has an animation in progress.
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        self.roundness_animation.is_some()
+    }
+
+    /// The contrast ratio of interface text on the application background, and whether it
+    /// meets the configured contrast standard.
+    fn text_contrast_badge(&self) -> Element<'_, Message> {
+        let theme = self.theme_builder.clone().build();
+        let ratio = contrast_ratio(theme.background.on.color, theme.background.base.color);
+        let standard = self.config.contrast_standard();
+        let ratio_text = format!("{ratio:.1}");
+        let minimum = format!("{:.1}", standard.min_ratio());
+
+        let (icon_name, status) = if standard.passes(ratio) {
+            (
+                "emblem-ok-symbolic",
+                fl!("contrast-pass", ratio = ratio_text, minimum = minimum),
+            )
+        } else {
+            (
+                "dialog-warning-symbolic",
+                fl!("contrast-warning", ratio = ratio_text, minimum = minimum),
+            )
+        };
+
+        row::with_capacity(2)
+            .push(icon::from_name(icon_name).size(16).icon())
+            .push(text::body(status).width(Length::Fill))
+            .spacing(self.theme_builder.spacing.space_xs)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .apply(container)
+            .padding(self.theme_builder.spacing.space_xs)
+            .style(cosmic::theme::Container::List)
+            .into()
+    }
+
+    /// A color picker for a color which affects text contrast, below the current contrast.
+    fn contrast_context_view(
+        &self,
+        on_update: fn(ColorPickerUpdate) -> Message,
+        model: impl Fn(&Self) -> &ColorPickerModel,
+    ) -> Element<'_, crate::pages::Message> {
+        let badge = self
+            .text_contrast_badge()
+            .apply(container)
+            .padding([0, self.theme_builder.spacing.space_l])
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance);
+
+        cosmic::widget::column::with_capacity(2)
+            .push(self.color_picker_context_view(
+                None,
+                RESET_TO_DEFAULT.as_str().into(),
+                on_update,
+                model,
+            ))
+            .push(badge)
+            .into()
+    }
+
+    /// The window hint blended into the application background by its opacity, since
+    /// themes only hold opaque hint colors.
+    fn blended_window_hint(&self, hint: Srgb) -> Srgb {
+        let opacity = self.window_hint_opacity.clamp(0.0, 1.0);
+        let background = self.application_color().into_linear::<f32>();
+        Srgb::from_linear(background * (1.0 - opacity) + hint.into_linear::<f32>() * opacity)
+    }
+
+    /// A slider for the opacity of a color, beside the color drawn over a checkerboard.
+    fn opacity_section(
+        &self,
+        label: String,
+        color: Option<Srgb>,
+        opacity: f32,
+        on_change: fn(f32) -> Message,
+    ) -> Element<'_, Message> {
+        let swatch = color.map(|color| {
+            let color = Color::from_rgba(color.red, color.green, color.blue, opacity);
+            checkered_swatch(color, 48, 24)
+        });
+
+        settings::view_section("")
+            .add(
+                settings::item::builder(label).flex_control(
+                    row::with_capacity(3)
+                        .push_maybe(swatch)
+                        .push(text(format!("{:.0}%", opacity * 100.0)))
+                        .push(
+                            cosmic::widget::slider(0.1..=1.0, opacity, on_change)
+                                .step(0.01)
+                                .width(Length::Fill),
+                        )
+                        .spacing(self.theme_builder.spacing.space_s)
+                        .align_items(cosmic::iced_core::Alignment::Center),
+                ),
+            )
+            .apply(container)
+            .padding([0, self.theme_builder.spacing.space_l])
+            .into()
+    }
+
+    /// Stops of the application background gradient, each of which is set to the color
+    /// chosen in the picker above.
+    fn gradient_section(&self) -> Element<'_, Message> {
+        let gradient = self.background_gradient();
+
+        let mut section = settings::view_section(fl!("app-background", "gradient")).add(
+            settings::item::builder(fl!("app-background", "use-gradient"))
+                .description(fl!("app-background", "gradient-desc"))
+                .toggler(gradient.is_some(), Message::ApplicationGradient),
+        );
+
+        if let Some(gradient) = gradient {
+            for (index, stop) in gradient.stops.iter().enumerate() {
+                section = section.add(
+                    settings::item::builder(fl!("app-background", "stop", number = index + 1))
+                        .control(
+                            row::with_capacity(2)
+                                .push(color_button(None, Color::from(*stop), false, 48, 24))
+                                .push(
+                                    button::text(fl!("app-background", "set-stop"))
+                                        .on_press(Message::GradientStop(index)),
+                                )
+                                .spacing(self.theme_builder.spacing.space_xs)
+                                .align_items(cosmic::iced_core::Alignment::Center),
+                        ),
+                );
+            }
+
+            let stops = gradient.stops.len();
+            section = section.add(
+                settings::item_row(vec![
+                    button::text(fl!("app-background", "add-stop"))
+                        .on_press_maybe(
+                            (stops < gradient::MAX_STOPS).then_some(Message::AddGradientStop),
+                        )
+                        .into(),
+                    button::text(fl!("app-background", "remove-stop"))
+                        .on_press_maybe(
+                            (stops > gradient::MIN_STOPS).then_some(Message::RemoveGradientStop),
+                        )
+                        .into(),
+                ])
+                .spacing(self.theme_builder.spacing.space_xs),
+            );
+        }
+
+        section
+            .apply(container)
+            .padding([0, self.theme_builder.spacing.space_l])
+            .into()
+    }
+
+    /// The text tint with its saturation reduced to the chosen intensity.
+    fn intensified_text_tint(&self) -> Option<Srgb> {
+        let tint = self.interface_text.get_applied_color().map(Srgb::from)?;
+        let mut hsv = Hsv::from_color(tint);
+        hsv.saturation *= self.text_tint_intensity / 100.0;
+        Some(Srgb::from_color(hsv))
+    }
+
+    fn custom_roundness_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let radii = &self.theme_builder.corner_radii;
+
+        let spin_button = |size: RadiusSize, corner: Corner| {
+            let radius = size.radius(radii)[corner as usize];
+            cosmic::widget::spin_button(format!("{radius:.0}"), move |msg| {
+                Message::CornerRadius(size, corner, msg)
+            })
+        };
+
+        let mut sections: Vec<Element<'_, Message>> = vec![settings::view_section("")
+            .add(
+                settings::item::builder(fl!("style", "link-corners"))
+                    .toggler(self.link_corners, Message::LinkCorners),
+            )
+            .into()];
+
+        if self.link_corners {
+            let section =
+                RadiusSize::ALL
+                    .into_iter()
+                    .fold(settings::view_section(""), |section, size| {
+                        section.add(
+                            settings::item::builder(size.title())
+                                .control(spin_button(size, Corner::TopLeft)),
+                        )
+                    });
+
+            sections.push(section.into());
+        } else {
+            sections.extend(RadiusSize::ALL.into_iter().map(|size| {
+                Corner::ALL
+                    .into_iter()
+                    .fold(settings::view_section(size.title()), |section, corner| {
+                        section.add(
+                            settings::item::builder(corner.title())
+                                .control(spin_button(size, corner)),
+                        )
+                    })
+                    .into()
+            }));
+        }
+
+        cosmic::widget::column::with_children(sections)
+            .spacing(self.theme_builder.spacing.space_m)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    /// The text tint picker, with an intensity slider and a live sample of tinted text.
+    fn text_tint_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let picker =
+            self.contrast_context_view(Message::InterfaceText, |this| &this.interface_text);
+
+        let Some(tint) = self.intensified_text_tint() else {
+            return picker;
+        };
+
+        // Built on each change of the slider, without writing the theme.
+        let mut builder = self.theme_builder.clone();
+        builder.text_tint = Some(tint);
+        let theme = builder.build();
+        let text_color = Color::from(theme.background.on);
+        let background = Color::from(theme.background.base);
+        let radius = theme.corner_radii.radius_s;
+
+        let intensity = settings::item::builder(fl!("text-tint", "intensity")).flex_control(
+            row::with_capacity(2)
+                .push(text(format!("{:.0}%", self.text_tint_intensity)))
+                .push(
+                    cosmic::widget::slider(
+                        0.0..=100.0,
+                        self.text_tint_intensity,
+                        Message::TextTintIntensity,
+                    )
+                    .width(Length::Fill),
+                )
+                .spacing(self.theme_builder.spacing.space_s)
+                .align_items(cosmic::iced_core::Alignment::Center),
+        );
+
+        let sample = text::body(fl!("text-tint", "sample"))
+            .width(Length::Fill)
+            .apply(container)
+            .padding(self.theme_builder.spacing.space_s)
+            .style(cosmic::theme::Container::custom(move |_| {
+                cosmic::widget::container::Appearance {
+                    text_color: Some(text_color),
+                    background: Some(Background::Color(background)),
+                    border: cosmic::iced_core::Border {
+                        radius: radius.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            }));
+
+        let preview = cosmic::widget::column::with_capacity(3)
+            .push(settings::view_section("").add(intensity))
+            .push(sample)
+            .push(button::suggested(fl!("text-tint", "apply")).on_press_maybe(
+                (self.text_tint_intensity < 100.0).then_some(Message::ApplyTextTintIntensity),
+            ))
+            .padding([0, self.theme_builder.spacing.space_l])
+            .spacing(self.theme_builder.spacing.space_s)
+            .width(Length::Fill)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance);
+
+        cosmic::widget::column::with_capacity(2)
+            .push(picker)
+            .push(preview)
+            .into()
+    }
+
+    /// Fields for giving an application its own icon theme.
+    fn icon_override_controls(&self) -> Element<'_, Message> {
+        let selected = self.icon_override_theme.as_ref().and_then(|selected| {
+            self.icon_themes
+                .iter()
+                .position(|theme| theme.id == *selected)
+        });
+        let app_id = self.icon_override_app.trim();
+
+        row::with_capacity(3)
+            .push(
+                cosmic::widget::text_input(
+                    fl!("icon-theme-overrides", "app-id"),
+                    &self.icon_override_app,
+                )
+                .on_input(Message::IconOverrideApp)
+                .width(Length::Fixed(160.0)),
+            )
+            .push(dropdown(
+                &self.icon_theme_names,
+                selected,
+                Message::IconOverrideTheme,
+            ))
+            .push(
+                button::standard(fl!("icon-theme-overrides", "add-button")).on_press_maybe(
+                    selected
+                        .filter(|_| !app_id.is_empty())
+                        .map(|id| Message::AddIconOverride(app_id.to_owned(), id)),
+                ),
+            )
+            .spacing(self.theme_builder.spacing.space_xxs)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .into()
+    }
+
+    fn experimental_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let active = self.icon_theme_active;
+        let filter = self.icon_theme_filter.trim().to_lowercase();
+        let theme = cosmic::theme::active();
+        let theme = theme.cosmic();
+        cosmic::iced::widget::column![
+            // Export theme choice
+            settings::view_section("")
+                .add(
+                    settings::item::builder(fl!("preview-mode"))
+                        .description(fl!("preview-mode", "desc"))
+                        .toggler(self.preview.is_some(), Message::PreviewMode)
+                )
+                .add(
+                    settings::item::builder(fl!("enable-export"))
+                        .description(fl!("enable-export", "desc"))
+                        .toggler(self.tk.apply_theme_global, Message::ApplyThemeGlobal)
+                )
+                .add(
+                    settings::item::builder(fl!("gtk-theme"))
+                        .description(fl!("gtk-theme", "desc"))
+                        .control(name_dropdown(
+                            &self.gtk_themes,
+                            self.gtk_theme_active.as_deref(),
+                            Message::GtkTheme
+                        ))
+                )
+                .add(
+                    settings::item::builder(fl!("confirm-overwrite", "always")).toggler(
+                        self.config.overwrite_without_confirm(),
+                        Message::OverwriteWithoutConfirm
+                    )
+                )
+                .add(
+                    settings::item::builder(fl!("export-gtk-css"))
+                        .description(fl!("export-gtk-css", "desc"))
+                        .control(button::standard(fl!("export")).on_press(Message::ExportGtkCss))
+                )
+                .add(
+                    settings::item::builder(fl!("terminal-palette"))
+                        .description(fl!("terminal-palette", "desc"))
+                        .control(
+                            row::with_capacity(2)
+                                .push(dropdown(
+                                    &self.terminal_formats,
+                                    TerminalFormat::ALL
+                                        .iter()
+                                        .position(|format| *format == self.terminal_format),
+                                    |i| Message::TerminalFormat(TerminalFormat::ALL[i]),
+                                ))
+                                .push(
+                                    button::standard(fl!("export")).on_press(
+                                        Message::ExportTerminalPalette(self.terminal_format)
+                                    )
+                                )
+                                .spacing(theme.space_xs())
+                        )
+                )
+                .add(
+                    settings::item::builder(fl!("export-diagnostics"))
+                        .description(fl!("export-diagnostics", "desc"))
+                        .control(
+                            button::standard(fl!("export")).on_press(Message::ExportDiagnostics)
+                        )
+                )
+                .add(
+                    settings::item::builder(fl!("contrast-standard"))
+                        .description(fl!("contrast-standard", "desc"))
+                        .control(dropdown(
+                            &self.contrast_standards,
+                            ContrastStandard::ALL
+                                .iter()
+                                .position(|s| *s == self.config.contrast_standard()),
+                            |i| Message::ContrastStandard(ContrastStandard::ALL[i]),
+                        ))
+                )
+                .add(
+                    settings::item::builder(fl!("color-vision"))
+                        .description(fl!("color-vision", "desc"))
+                        .control(dropdown(
+                            &self.color_visions,
+                            ColorVisionMode::ALL
+                                .iter()
+                                .position(|mode| *mode == self.color_vision),
+                            |i| Message::ColorVision(ColorVisionMode::ALL[i]),
+                        ))
+                )
+                .add(
+                    settings::item::builder(fl!("icon-symbolic-preview"))
+                        .description(fl!("icon-symbolic-preview", "desc"))
+                        .toggler(self.icon_symbolic_preview, Message::IconSymbolicPreview)
+                )
+                .add(
+                    settings::item::builder(fl!("icon-diagnostics"))
+                        .description(fl!("icon-diagnostics", "desc"))
+                        .toggler(self.icon_diagnostics, Message::IconDiagnostics)
+                ),
+            // Appearance snapshots
+            self.snapshots.iter().fold(
+                settings::view_section(fl!("snapshots")).add(
+                    settings::item::builder(fl!("snapshots", "take"))
+                        .description(fl!("snapshots", "desc"))
+                        .control(
+                            button::standard(fl!("snapshots", "take-button"))
+                                .on_press(Message::TakeSnapshot)
+                        )
+                ),
+                |section, (id, timestamp)| {
+                    section.add(
+                        settings::item::builder(timestamp.as_str()).control(
+                            row::with_capacity(2)
+                                .push_maybe(
+                                    self.snapshot_thumbnails
+                                        .get(id)
+                                        .map(|thumbnail| thumbnail.view()),
+                                )
+                                .push(
+                                    button::standard(fl!("snapshots", "restore")).on_press_maybe(
+                                        (!self.read_only).then_some(Message::RestoreSnapshot(*id)),
+                                    ),
+                                )
+                                .spacing(self.theme_builder.spacing.space_s)
+                                .align_items(cosmic::iced_core::Alignment::Center),
+                        ),
+                    )
+                }
+            ),
+            // Named looks which can be switched between in one action
+            self.look_profiles.iter().fold(
+                settings::view_section(fl!("look-profiles")).add(
+                    settings::item::builder(fl!("look-profiles", "save"))
+                        .description(fl!("look-profiles", "desc"))
+                        .control(
+                            row::with_capacity(2)
+                                .push(
+                                    cosmic::widget::text_input(
+                                        fl!("look-profiles", "name"),
+                                        &self.look_profile_name
+                                    )
+                                    .on_input(Message::LookProfileName)
+                                    .width(Length::Fixed(160.0))
+                                )
+                                .push(
+                                    button::standard(fl!("look-profiles", "save-button"))
+                                        .on_press_maybe(
+                                            (!self.look_profile_name.trim().is_empty()).then(
+                                                || Message::SaveLookProfile(
+                                                    self.look_profile_name.trim().to_owned()
+                                                )
+                                            )
+                                        )
+                                )
+                                .spacing(self.theme_builder.spacing.space_xxs)
+                                .align_items(cosmic::iced_core::Alignment::Center)
+                        )
+                ),
+                |section, name| {
+                    section.add(
+                        settings::item::builder(name.as_str()).control(
+                            row::with_capacity(2)
+                                .push(
+                                    button::standard(fl!("look-profiles", "load")).on_press_maybe(
+                                        (!self.read_only)
+                                            .then(|| Message::LoadLookProfile(name.clone())),
+                                    ),
+                                )
+                                .push(
+                                    button::icon(icon::from_name("edit-delete-symbolic"))
+                                        .on_press(Message::DeleteLookProfile(name.clone())),
+                                )
+                                .spacing(self.theme_builder.spacing.space_xxs)
+                                .align_items(cosmic::iced_core::Alignment::Center),
+                        ),
+                    )
+                }
+            ),
+            // Directories skipped when searching for icon themes
+            self.config.icon_scan_excludes().iter().fold(
+                settings::view_section(fl!("icon-scan-excludes")).add(
+                    settings::item::builder(fl!("icon-scan-excludes", "add"))
+                        .description(fl!("icon-scan-excludes", "desc"))
+                        .control(
+                            button::standard(fl!("icon-scan-excludes", "add-button"))
+                                .on_press(Message::StartIconScanExclude)
+                        )
+                ),
+                |section, dir| {
+                    section.add(
+                        settings::item::builder(dir.display().to_string()).control(
+                            button::icon(icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::RemoveIconScanExclude(dir.clone())),
+                        ),
+                    )
+                }
+            ),
+            // Icon themes used by particular applications
+            self.config.icon_theme_overrides().iter().fold(
+                settings::view_section(fl!("icon-theme-overrides")).add(
+                    settings::item::builder(fl!("icon-theme-overrides", "add"))
+                        .description(fl!("icon-theme-overrides", "desc"))
+                        .control(self.icon_override_controls())
+                ),
+                |section, (app_id, theme_id)| {
+                    let theme_name = self
+                        .icon_themes
+                        .iter()
+                        .find(|theme| theme.id == *theme_id)
+                        .map_or(theme_id.as_str(), |theme| theme.name.as_str());
+                    section.add(
+                        settings::item::builder(app_id.as_str())
+                            .description(theme_name)
+                            .control(
+                                button::icon(icon::from_name("edit-delete-symbolic"))
+                                    .on_press(Message::RemoveIconOverride(app_id.clone())),
+                            ),
+                    )
+                }
+            ),
+            // Icon theme previews
+            cosmic::widget::column::with_children(vec![
+                row::with_capacity(6)
+                    .push(text::heading(&*ICON_THEME))
+                    .push(horizontal_space(Length::Fill))
+                    .push(ICON_PREVIEW_SIZES.into_iter().fold(
+                        row::with_capacity(ICON_PREVIEW_SIZES.len()).spacing(theme.space_xs()),
+                        |row, size| row.push(radio(
+                            fl!("icon-preview-size", size = size),
+                            size,
+                            Some(self.icon_preview_size),
+                            Message::IconPreviewSize,
+                        ))
+                    ))
+                    .push(dropdown(
+                        &self.icon_theme_sorts,
+                        IconThemeSort::ALL
+                            .iter()
+                            .position(|s| *s == self.config.icon_theme_sort()),
+                        |i| Message::IconThemeSort(IconThemeSort::ALL[i]),
+                    ))
+                    .push(
+                        button::icon(icon::from_name(if self.icon_refresh_in_flight {
+                            "process-working-symbolic"
+                        } else {
+                            "view-refresh-symbolic"
+                        }))
+                        .on_press_maybe(
+                            (!self.icon_refresh_in_flight).then_some(Message::RefreshIconThemes)
+                        )
+                    )
+                    .push(
+                        button::text(fl!("icon-theme-import"))
+                            .on_press(Message::StartIconThemeImport)
+                    )
+                    .align_items(cosmic::iced_core::Alignment::Center)
+                    .into(),
+                cosmic::widget::search_input(fl!("icon-theme-filter"), &self.icon_theme_filter)
+                    .on_input(Message::IconThemeFilter)
+                    .on_clear(Message::IconThemeFilter(String::new()))
+                    .into(),
+                flex_row(
+                    self.icon_themes
+                        .iter()
+                        .zip(self.icon_handles.iter())
+                        .enumerate()
+                        // The active theme stays visible so that the selection is never hidden.
+                        .filter(|(i, (theme, _))| {
+                            active == Some(*i)
+                                || filter.is_empty()
+                                || theme.name.to_lowercase().contains(&filter)
+                        })
+                        .map(|(i, (theme, handles))| {
+                            let selected = active.map(|j| i == j).unwrap_or_default();
+                            // The active theme is previewed in the symbolic style when requested.
+                            let handles = match self.symbolic_handles {
+                                Some((ref id, ref symbolic))
+                                    if selected
+                                        && self.icon_symbolic_preview
+                                        && *id == theme.id =>
+                                {
+                                    symbolic
+                                }
+                                _ => handles,
+                            };
+                            icon_theme_button(
+                                &theme.name,
+                                theme.comment.as_deref(),
+                                handles,
+                                i,
+                                selected,
+                                self.icon_preview_size,
+                                self.icon_diagnostics,
+                            )
+                        })
+                        .collect(),
+                )
+                .row_spacing(theme.space_xs())
+                .column_spacing(theme.space_xxxs())
+                .into()
+            ])
+            .spacing(theme.space_xxs()),
+            // Cursor theme previews
+            cosmic::widget::column::with_children(vec![
+                text::heading(fl!("cursor-theme")).into(),
+                flex_row(
+                    self.cursor_themes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, theme)| {
+                            cursor_theme_button(theme, i, self.cursor_theme_active == Some(i))
+                        })
+                        .collect(),
+                )
+                .row_spacing(theme.space_xs())
+                .column_spacing(theme.space_xxxs())
+                .into()
+            ])
+            .spacing(theme.space_xxs())
+        ]
+        .spacing(theme.space_m())
+        .width(Length::Fill)
+        .apply(Element::from)
+        .map(crate::pages::Message::Appearance)
+    }
+
+    /// Handles a message, recording the theme it replaces in the undo history.
+    pub fn update(&mut self, message: Message) -> Command<app::Message> {
+        let previous = (message.modifies_theme()
+            && !matches!(message, Message::Undo | Message::Redo))
+        .then(|| self.theme_builder.clone());
+
+        let ret = self.update_untracked(message);
+
+        if let Some(previous) = previous {
+            if previous != self.theme_builder {
+                if self.undo_history.len() == HISTORY_LIMIT {
+                    self.undo_history.remove(0);
+                }
+
+                self.undo_history.push(previous);
+                self.redo_history.clear();
+            }
+        }
+
+        ret
+    }
+
+    /// Handles a message without recording history, so that messages handled on behalf of
+    /// another are undone together with it.
+    #[allow(clippy::too_many_lines)]
+    fn update_untracked(&mut self, message: Message) -> Command<app::Message> {
+        if self.read_only && message.modifies_theme() {
+            return Command::none();
+        }
+
+        // Anything other than further edits sees the theme as it was written.
+        if self.theme_write_pending
+            && !message.modifies_theme()
+            && !matches!(message, Message::FlushThemeWrite(_))
+        {
+            let flush = self.write_theme();
+            return Command::batch(vec![flush, self.update_untracked(message)]);
+        }
+
+        self.theme_builder_needs_update = false;
+        let mut needs_sync = false;
+
+        let mut ret = match message {
+            Message::Undo => {
+                let Some(builder) = self.undo_history.pop() else {
+                    return Command::none();
+                };
+
+                let current = std::mem::replace(&mut self.theme_builder, builder);
+                self.redo_history.push(current);
+                needs_sync = true;
+                self.restore_history()
+            }
+            Message::Redo => {
+                let Some(builder) = self.redo_history.pop() else {
+                    return Command::none();
+                };
+
+                let current = std::mem::replace(&mut self.theme_builder, builder);
+                self.undo_history.push(current);
+                needs_sync = true;
+                self.restore_history()
+            }
+            Message::DarkMode(enabled) if self.preview.is_some() => {
+                // Preview the saved theme of the other mode, without switching modes on disk.
+                let mut theme_mode = self.theme_mode;
+                theme_mode.is_dark = enabled;
+                let (theme_builder_config, theme_builder) = load_theme_builder(enabled);
+                self.theme_mode = theme_mode;
+                self.theme_builder_config = theme_builder_config;
+                self.preview = Some(theme_builder);
+                self.reload_theme_mode();
+                self.write_theme()
+            }
+            Message::ToggleDarkMode => {
+                self.update_untracked(Message::DarkMode(!self.theme_mode.is_dark))
+            }
+            Message::DarkMode(enabled) => {
+                // The history holds themes of the mode being left.
+                self.undo_history.clear();
+                self.redo_history.clear();
+
+                if let Some(config) = self.theme_mode_config.as_ref() {
+                    if let Err(err) = self.theme_mode.set_is_dark(config, enabled) {
+                        tracing::error!(?err, "Error setting dark mode");
+                    }
+
+                    self.reload_theme_mode();
+                }
+
+                if self.tk.apply_theme_global {
+                    gnome_color_scheme_command(enabled)
+                } else {
+                    Command::none()
+                }
+            }
+            Message::Autoswitch(enabled) => {
+                self.theme_mode.auto_switch = enabled;
+                if self.preview.is_none() {
+                    if let Some(config) = self.theme_mode_config.as_ref() {
+                        _ = config.set::<bool>("auto_switch", enabled);
+                    }
+                }
+                self.follow_schedule()
+            }
+            Message::AutoSwitchSchedule => {
+                self.context_view = Some(ContextView::AutoSwitchSchedule);
+                cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                    fl!("auto-switch", "schedule").into(),
+                ))
+            }
+            Message::AutoSwitchCustom(custom) => {
+                let schedule = custom.then(|| self.auto_switch_schedule.unwrap_or_default());
+                self.set_schedule(schedule)
+            }
+            Message::AutoSwitchLightTime(light) => {
+                let schedule = self
+                    .auto_switch_schedule
+                    .map(|schedule| Schedule { light, ..schedule });
+                self.set_schedule(schedule)
+            }
+            Message::AutoSwitchDarkTime(dark) => {
+                let schedule = self
+                    .auto_switch_schedule
+                    .map(|schedule| Schedule { dark, ..schedule });
+                self.set_schedule(schedule)
+            }
+            Message::AccentWindowHint(u) => {
+                needs_sync = true;
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::AccentWindowHint,
+                    fl!("window-hint-accent").into(),
+                );
+                Command::batch(vec![cmd, self.accent_window_hint.update::<app::Message>(u)])
+            }
+            Message::IconTheme(id) => {
+                let mut cmd = Command::none();
+
+                if let Some(theme) = self.icon_themes.get(id).cloned() {
+                    self.icon_theme_active = Some(id);
+                    self.tk.icon_theme = theme.id.clone();
+
+                    if let Err(why) = self.config.icon_theme_used(theme.id.clone()) {
+                        tracing::error!(?why, "failed to store icon theme usage");
+                    }
+
+                    // Written along with the theme when the preview is committed.
+                    if self.preview.is_none() {
+                        if let Some(ref config) = self.tk_config {
+                            let _ = self.tk.write_entry(config);
+                        }
+
+                        cmd = gnome_icon_theme_command(theme.name);
+                    }
+                }
+
+                Command::batch(vec![cmd, self.load_symbolic_handles()])
+            }
+            Message::ColorSchemeGnomeResult(result) => {
+                if let Err(why) = result {
+                    tracing::error!(?why, "failed to set the GNOME color scheme");
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "color-scheme-gnome-failed"
+                    )));
+                }
+
+                Command::none()
+            }
+            Message::IconThemeGnomeResult(result) => {
+                if let Err(why) = result {
+                    tracing::error!(?why, "failed to set the GNOME icon theme");
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "icon-theme-gnome-failed"
+                    )));
+                }
+
+                Command::none()
+            }
+            Message::WindowHintSize(msg) => {
+                needs_sync = true;
+                self.theme_builder_needs_update = true;
+                self.size_drafts.remove(&SizeField::ActiveHint);
+                self.theme_builder.active_hint =
+                    step_size(self.theme_builder.active_hint, MAX_ACTIVE_HINT, msg);
+                Command::none()
+            }
+            Message::CornerRadius(size, corner, msg) => {
+                needs_sync = true;
+                let mut radii = self.theme_builder.corner_radii;
+                let radius = size.radius_mut(&mut radii);
+                let current = radius[corner as usize].round();
+                let value = match msg {
+                    spin_button::Message::Increment => current + 1.0,
+                    spin_button::Message::Decrement => (current - 1.0).max(0.0),
+                };
+
+                if self.link_corners {
+                    *radius = [value; 4];
+                } else {
+                    radius[corner as usize] = value;
+                }
+
+                self.roundness_animation = None;
+                self.roundness = radii.into();
+                self.theme_builder.corner_radii = radii;
+                self.theme_builder_needs_update = true;
+                if self.preview.is_none() {
+                    Self::update_panel_radii(self.roundness);
+                }
+                Command::none()
+            }
+            Message::LinkCorners(link) => {
+                self.link_corners = link;
+                Command::none()
+            }
+            Message::CustomRoundness => {
+                self.context_view = Some(ContextView::CustomRoundness);
+                cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                    fl!("style", "custom").into(),
+                ))
+            }
+            Message::GapSize(msg) => {
+                needs_sync = true;
+                self.theme_builder_needs_update = true;
+                self.size_drafts.remove(&SizeField::Gap);
+                self.theme_builder.gaps.1 = step_size(self.theme_builder.gaps.1, MAX_GAP, msg);
+                Command::none()
+            }
+            Message::OuterGapSize(msg) => {
+                needs_sync = true;
+                self.theme_builder_needs_update = true;
+                self.size_drafts.remove(&SizeField::OuterGap);
+                self.theme_builder.gaps.0 = step_size(self.theme_builder.gaps.0, MAX_GAP, msg);
+                Command::none()
+            }
+            Message::SizeInput(field, input) => {
+                self.size_drafts.insert(field, input);
+                Command::none()
+            }
+            Message::SizeSubmit(field) => {
+                // Input which is not a number is discarded, showing the previous size again.
+                if let Some(size) = self
+                    .size_drafts
+                    .remove(&field)
+                    .and_then(|input| input.trim().parse::<u32>().ok())
+                {
+                    *field.value_mut(&mut self.theme_builder) = size.min(field.max());
+                    needs_sync = true;
+                    self.theme_builder_needs_update = true;
+                }
+
+                Command::none()
+            }
+            Message::ApplicationGradient(enabled) => {
+                let gradient =
+                    enabled.then(|| BackgroundGradient::from_color(self.application_color()));
+                self.set_background_gradient(gradient);
+                Command::none()
+            }
+            Message::AddGradientStop => {
+                if let Some(mut gradient) = self.background_gradient().cloned() {
+                    if gradient.stops.len() < gradient::MAX_STOPS {
+                        gradient.stops.push(self.application_color());
+                        self.set_background_gradient(Some(gradient));
+                    }
+                }
+                Command::none()
+            }
+            Message::RemoveGradientStop => {
+                if let Some(mut gradient) = self.background_gradient().cloned() {
+                    if gradient.stops.len() > gradient::MIN_STOPS {
+                        gradient.stops.pop();
+                        self.set_background_gradient(Some(gradient));
+                    }
+                }
+                Command::none()
+            }
+            Message::GradientStop(index) => {
+                if let Some(mut gradient) = self.background_gradient().cloned() {
+                    if let Some(stop) = gradient.stops.get_mut(index) {
+                        *stop = self.application_color();
+                        self.set_background_gradient(Some(gradient));
+                    }
+                }
+                Command::none()
+            }
+            Message::ApplicationBackground(u) => {
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::ApplicationBackground,
+                    fl!("app-background").into(),
+                );
+
+                Command::batch(vec![
+                    cmd,
+                    self.application_background.update::<app::Message>(u),
+                ])
+            }
+            Message::ContainerBackground(u) => {
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::ContainerBackground,
+                    fl!("container-background").into(),
+                );
+
+                Command::batch(vec![
+                    cmd,
+                    self.container_background.update::<app::Message>(u),
+                ])
+            }
+            Message::CustomAccent(u) => {
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::CustomAccent,
+                    fl!("accent-color").into(),
+                );
+
+                let cmd2 = self.custom_accent.update::<app::Message>(u);
+
+                self.theme_builder.accent = self.custom_accent.get_applied_color().map(Srgb::from);
+                Command::batch(vec![cmd, cmd2])
+            }
+            Message::InterfaceText(u) => {
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::InterfaceText,
+                    fl!("text-tint").into(),
+                );
+
+                Command::batch(vec![cmd, self.interface_text.update::<app::Message>(u)])
+            }
+            Message::TextTintIntensity(intensity) => {
+                self.text_tint_intensity = intensity;
+                Command::none()
+            }
+            Message::ApplyTextTintIntensity => {
+                if let Some(tint) = self.intensified_text_tint() {
+                    set_picker_color(&mut self.interface_text, tint);
+                    self.theme_builder_needs_update = true;
+                }
+                self.text_tint_intensity = 100.0;
+                Command::none()
+            }
+            Message::ControlComponent(u) => {
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::ControlComponent,
+                    fl!("control-tint").into(),
+                );
+                Command::batch(vec![cmd, self.control_component.update::<app::Message>(u)])
+            }
+            Message::SelectionColor(u) => {
+                let cmd = self.update_color_picker(
+                    &u,
+                    ContextView::SelectionColor,
+                    fl!("selection-color").into(),
+                );
+                Command::batch(vec![cmd, self.selection_color.update::<app::Message>(u)])
+            }
+            Message::SyncShape(enabled) => {
+                if let Err(why) = self.config.set_sync_layout(enabled) {
+                    tracing::error!(?why, "failed to store the layout sync setting");
+                }
+
+                // Syncing again makes the other mode match the layout being edited.
+                needs_sync = enabled;
+                Command::none()
+            }
+            Message::SeparateModeAccents(enabled) => {
+                if let Err(why) = self.config.set_separate_mode_accents(enabled) {
+                    tracing::error!(?why, "failed to store the separate mode accents setting");
+                }
+
+                if enabled {
+                    self.load_mode_accents();
+                }
+
+                Command::none()
+            }
+            Message::DayAccent(u) => self.update_mode_accent(false, u),
+            Message::NightAccent(u) => self.update_mode_accent(true, u),
+            Message::Roundness(r) => {
+                needs_sync = true;
+                self.roundness_animation = Some(RoundnessAnimation {
+                    from: self.theme_builder.corner_radii,
+                    to: r.into(),
+                    start: Instant::now(),
+                });
+                self.roundness = r;
+                self.theme_builder.corner_radii = self.roundness.into();
+                self.theme_builder_needs_update = true;
+                if self.preview.is_none() {
+                    Self::update_panel_radii(r);
+                }
+                Command::none()
+            }
+            Message::Entered((icon_themes, icon_handles)) => {
+                self.reload_configs();
+
+                // Set the icon themes, and define the active icon theme.
+                self.icon_themes = icon_themes;
+                self.icon_handles = icon_handles;
+                self.sort_icon_themes();
+                self.load_snapshots();
+                self.look_profiles = profile::list();
+                self.load_recent_theme_files();
+
+                let excludes = self.config.icon_scan_excludes().to_vec();
+                let cursor_cmd = Command::perform(cursor::fetch(icon_dirs(&excludes)), |themes| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::CursorThemesScanned(themes),
+                    ))
+                });
+
+                let font_cmds = [FontKind::Interface, FontKind::Monospace].map(|kind| {
+                    Command::perform(font::families(kind), move |families| {
+                        crate::Message::PageMessage(crate::pages::Message::Appearance(
+                            Message::FontFamilies(kind, families),
+                        ))
+                    })
+                });
+
+                let gtk_cmd = Command::perform(gtk_theme::fetch(), |themes| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::GtkThemesScanned(themes),
+                    ))
+                });
+
+                Command::batch(
+                    [cursor_cmd, gtk_cmd, self.follow_schedule()]
+                        .into_iter()
+                        .chain(font_cmds),
+                )
+            }
+            Message::CursorThemesScanned((themes, active)) => {
+                self.cursor_theme_active =
+                    active.and_then(|active| themes.iter().position(|theme| theme.id == active));
+                self.cursor_themes = themes;
+                Command::none()
+            }
+            Message::CursorTheme(id) => {
+                let Some(theme) = self.cursor_themes.get(id) else {
+                    return Command::none();
+                };
+
+                self.cursor_theme_active = Some(id);
+                Command::perform(
+                    cursor::set_default(user_icon_dir(), theme.id.clone()),
+                    |result| {
+                        crate::Message::PageMessage(crate::pages::Message::Appearance(
+                            Message::CursorThemeResult(result),
+                        ))
+                    },
+                )
+            }
+            Message::CursorThemeResult(result) => {
+                if let Err(why) = result {
+                    tracing::error!(?why, "failed to set the cursor theme");
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "cursor-theme",
+                        "failed"
+                    )));
+                }
+
+                Command::none()
+            }
+            Message::GtkThemesScanned((themes, active)) => {
+                self.gtk_themes = themes.into();
+                self.gtk_theme_active =
+                    active.or_else(|| self.config.gtk_theme().map(String::from));
+                Command::none()
+            }
+            Message::GtkTheme(id) => {
+                if let Err(why) = self.config.set_gtk_theme(id.clone()) {
+                    tracing::error!(?why, "failed to store the GTK theme");
+                }
+
+                self.gtk_theme_active = Some(id.clone());
+                Command::perform(gtk_theme::set(id), |result| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::GtkThemeResult(result),
+                    ))
+                })
+            }
+            Message::GtkThemeResult(result) => {
+                if let Err(why) = result {
+                    tracing::error!(?why, "failed to set the GTK theme");
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "gtk-theme",
+                        "failed"
+                    )));
+                }
+
+                Command::none()
+            }
+            Message::FontFamilies(kind, families) => {
+                match kind {
+                    FontKind::Interface => self.font_families = families.into(),
+                    FontKind::Monospace => self.monospace_families = families.into(),
+                }
+
+                Command::none()
+            }
+            Message::InterfaceFont(family) => {
+                if let Err(why) = self.config.set_interface_font(family.clone()) {
+                    tracing::error!(?why, "failed to store the interface font");
+                }
+
+                gnome_font_command(FontKind::Interface, family)
+            }
+            Message::MonospaceFont(family) => {
+                if let Err(why) = self.config.set_monospace_font(family.clone()) {
+                    tracing::error!(?why, "failed to store the monospace font");
+                }
+
+                gnome_font_command(FontKind::Monospace, family)
+            }
+            Message::TextScaling(factor) => {
+                self.text_scaling = factor;
+                self.text_scaling_generation = self.text_scaling_generation.wrapping_add(1);
+                let generation = self.text_scaling_generation;
+
+                Command::perform(tokio::time::sleep(TEXT_SCALING_DEBOUNCE), move |()| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::TextScalingSettled(generation),
+                    ))
+                })
+            }
+            Message::FlushThemeWrite(generation) => {
+                if !self.theme_write_pending || generation != self.theme_write_generation {
+                    return Command::none();
+                }
+
+                self.write_theme()
+            }
+            Message::TextScalingSettled(generation) => {
+                if generation != self.text_scaling_generation {
+                    return Command::none();
+                }
+
+                if let Err(why) = self.config.set_text_scaling_factor(self.text_scaling) {
+                    tracing::error!(?why, "failed to store the text scaling factor");
+                }
+
+                Command::perform(font::set_gnome_text_scaling(self.text_scaling), |result| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::GnomeFontResult(result),
+                    ))
+                })
+            }
+            Message::GnomeFontResult(result) => {
+                if let Err(why) = result {
+                    tracing::error!(?why, "failed to set the GNOME font");
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "interface-font",
+                        "failed"
+                    )));
+                }
+
+                Command::none()
+            }
+            Message::RefreshIconThemes => self.rescan_icon_themes(),
+            Message::IconThemesFound((icon_themes, icon_handles)) => {
+                for (theme, handles) in icon_themes.into_iter().zip(icon_handles) {
+                    if !self.icon_themes.iter().any(|known| known.id == theme.id) {
+                        self.icon_themes.push(theme);
+                        self.icon_handles.push(handles);
+                    }
+                }
+
+                self.sort_icon_themes();
+                Command::none()
+            }
+            Message::IconThemesScanned((icon_themes, icon_handles)) => {
+                self.icon_refresh_in_flight = false;
+                self.icon_themes = icon_themes;
+                self.icon_handles = icon_handles;
+                self.sort_icon_themes();
+
+                let Some(icon_theme) = self.pending_icon_theme.take() else {
+                    return self.load_symbolic_handles();
+                };
+
+                // An icon theme which is still missing is never applied, and the current
+                // icon theme is kept.
+                match self.icon_themes.iter().position(|t| t.id == icon_theme) {
+                    Some(id) => self.update_untracked(Message::IconTheme(id)),
+                    None => Command::batch(vec![
+                        self.load_symbolic_handles(),
+                        cosmic::command::message(app::Message::Toast(fl!(
+                            "icon-theme-import",
+                            "missing",
+                            theme = icon_theme
+                        ))),
+                    ]),
+                }
+            }
+            Message::StartIconScanExclude => Command::perform(
+                async {
+                    SelectedFiles::open_file()
+                        .modal(true)
+                        .directory(true)
+                        .send()
+                        .await?
+                        .response()
+                },
+                |res| {
+                    let message = match res {
+                        Ok(f) => Message::IconScanExcludeDir(Arc::new(f)),
+                        Err(why) => {
+                            if !is_cancelled(&why) {
+                                tracing::error!(?why, "failed to select a directory to exclude");
+                            }
+                            Message::FileChooserClosed
+                        }
+                    };
+
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+                },
+            ),
+            Message::IconScanExcludeDir(f) => {
+                let Some(dir) = selected_path(&f) else {
+                    return Command::none();
+                };
+
+                if let Err(why) = self.config.add_icon_scan_exclude(dir) {
+                    tracing::error!(?why, "failed to store icon scan exclusions");
+                }
+
+                self.rescan_icon_themes()
+            }
+            Message::RemoveIconScanExclude(dir) => {
+                if let Err(why) = self.config.remove_icon_scan_exclude(&dir) {
+                    tracing::error!(?why, "failed to store icon scan exclusions");
+                }
+
+                self.rescan_icon_themes()
+            }
+            Message::IconOverrideApp(app_id) => {
+                self.icon_override_app = app_id;
+                Command::none()
+            }
+            Message::IconOverrideTheme(id) => {
+                self.icon_override_theme = self.icon_themes.get(id).map(|theme| theme.id.clone());
+                Command::none()
+            }
+            Message::AddIconOverride(app_id, id) => {
+                let Some(theme) = self.icon_themes.get(id) else {
+                    return Command::none();
+                };
+
+                // Desktop IDs are often copied with the extension of their desktop entry.
+                let app_id = app_id.trim();
+                let app_id = app_id.strip_suffix(".desktop").unwrap_or(app_id);
+                if let Err(why) = self
+                    .config
+                    .set_icon_theme_override(app_id.to_owned(), theme.id.clone())
+                {
+                    tracing::error!(?why, app_id, "failed to store an icon theme override");
+                }
+
+                self.icon_override_app.clear();
+                Command::none()
+            }
+            Message::RemoveIconOverride(app_id) => {
+                if let Err(why) = self.config.remove_icon_theme_override(&app_id) {
+                    tracing::error!(?why, app_id, "failed to remove an icon theme override");
+                }
+                Command::none()
+            }
+            Message::IconSymbolicPreview(enabled) => {
+                self.icon_symbolic_preview = enabled;
+                self.load_symbolic_handles()
+            }
+            Message::IconDiagnostics(enabled) => {
+                self.icon_diagnostics = enabled;
+                Command::none()
+            }
+            Message::IconPreviewSize(size) => {
+                self.icon_preview_size = size;
+                if let Err(why) = self.config.set_icon_preview_size(size) {
+                    tracing::error!(?why, "failed to store the icon preview size");
+                }
+                Command::none()
+            }
+            Message::IconSymbolicHandles(id, handles) => {
+                self.symbolic_handles = Some((id, *handles));
+                Command::none()
+            }
+            Message::IconThemeSort(sort) => {
+                if let Err(why) = self.config.set_icon_theme_sort(sort) {
+                    tracing::error!(?why, "failed to store icon theme sort");
+                }
+                self.sort_icon_themes();
+                Command::none()
+            }
+            Message::Left => {
+                // Leaving without committing discards the preview.
+                if self.preview.take().is_some() {
+                    self.discard_preview();
+                }
+
+                Command::perform(async {}, |()| {
+                    app::Message::SetTheme(cosmic::theme::system_preference())
+                })
+            }
+            Message::PreviewMode(true) => {
+                self.preview = Some(self.theme_builder.clone());
+                Command::none()
+            }
+            Message::PreviewMode(false) => {
+                if self.preview.take().is_some() {
+                    self.discard_preview();
+                }
+
+                cosmic::command::message(app::Message::SetTheme(cosmic::theme::system_preference()))
+            }
+            Message::CommitPreview => {
+                if self.preview.take().is_none() {
+                    return Command::none();
+                }
+
+                if let Some(config) = self.theme_mode_config.as_ref() {
+                    // The mode may already match in memory, so write it unconditionally.
+                    if let Err(err) = config.set::<bool>("is_dark", self.theme_mode.is_dark) {
+                        tracing::error!(?err, "Error setting dark mode");
+                    }
+                    _ = config.set::<bool>("auto_switch", self.theme_mode.auto_switch);
+                }
+
+                if let Some(ref config) = self.tk_config {
+                    let _ = self.tk.write_entry(config);
+                }
+
+                let gnome_cmd = self
+                    .icon_theme_active
+                    .and_then(|id| self.icon_themes.get(id))
+                    .map_or_else(Command::none, |theme| {
+                        gnome_icon_theme_command(theme.name.clone())
+                    });
+
+                Self::update_panel_radii(self.roundness);
+                needs_sync = true;
+                Command::batch(vec![gnome_cmd, self.write_theme()])
+            }
+            Message::PaletteAccent(c) => {
+                self.theme_builder.accent = Some(c.into());
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+            // Arrow keys only move between swatches while one of them has focus.
+            Message::MoveAccentFocus(step) => {
+                Command::widget(focusable::find_focused()).map(move |id| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::AccentFocusFrom(id, step),
+                    ))
+                })
+            }
+            Message::AccentFocusFrom(focused, step) => {
+                let Some(n) = next_accent_swatch(&focused, self.accent_swatches().len(), step)
+                else {
+                    return Command::none();
+                };
+
+                // One swatch before the focused one stays in view, to show there are more.
+                let swatch_stride = 48.0 + 16.0;
+                Command::batch(vec![
+                    Command::widget(focusable::focus(accent_swatch_id(n))),
+                    scrollable::scroll_to(
+                        self.accent_scrollable.clone(),
+                        scrollable::AbsoluteOffset {
+                            x: n.saturating_sub(1) as f32 * swatch_stride,
+                            y: 0.0,
+                        },
+                    ),
+                ])
+            }
+            Message::PaletteAccentAdd => {
+                if let Some(color) = self.custom_accent.get_applied_color() {
+                    if let Err(why) = self.config.add_saved_accent(Srgb::from(color)) {
+                        tracing::error!(?why, "failed to save an accent color");
+                    }
+                }
+                Command::none()
+            }
+            Message::PaletteAccentRemove(index) => {
+                if let Err(why) = self.config.remove_saved_accent(index) {
+                    tracing::error!(?why, "failed to remove a saved accent color");
+                }
+                Command::none()
+            }
+            Message::SelectResetScope(scope) => {
+                self.reset_scope = scope;
+                Command::none()
+            }
+            Message::ResetScope(scope) => {
+                self.theme_builder = scope.apply(&self.theme_builder, self.default_builder());
+                let cmd = self.write_theme();
+
+                if self.preview.is_none() && scope != ResetScope::Colors {
+                    Self::update_panel_radii(self.theme_builder.corner_radii.into());
+                }
+
+                self.reload_theme_mode();
+                cmd
+            }
+            Message::HighContrast(enabled) => {
+                let is_dark = self.theme_mode.is_dark;
+                set_high_contrast(&mut self.theme_builder, is_dark, enabled);
+                let cmd = self.write_theme();
+
+                // Both modes share the setting, so that switching modes keeps the contrast.
+                if self.preview.is_none() {
+                    let (_, mut other) = load_theme_builder(!is_dark);
+                    if is_high_contrast(&other) != enabled {
+                        set_high_contrast(&mut other, !is_dark, enabled);
+                        if let Err(why) = write_theme_builder(&other, !is_dark) {
+                            tracing::error!(?why, "failed to set the contrast of the other mode");
+                        }
+                    }
+                }
+
+                self.reload_theme_mode();
+                cmd
+            }
+            Message::StartImport => select_import_file(Message::ImportFile),
+            Message::ImportFromClipboard => cosmic::iced::clipboard::read(|contents| {
+                let message = match contents
+                    .filter(|contents| !contents.trim().is_empty())
+                    .ok_or(import::Error::EmptyClipboard)
+                    .and_then(|contents| ImportedTheme::parse(&contents))
+                {
+                    Ok(theme) => Message::ImportLoaded(theme, None),
+                    Err(why) => {
+                        tracing::error!(
+                            ?why,
+                            "failed to import a custom theme from the clipboard."
+                        );
+                        Message::ImportError(why)
+                    }
+                };
+
+                crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+            }),
+            Message::StartIconThemeImport => select_import_file(Message::IconThemeImportFile),
+            Message::StartExport => {
+                let is_dark = self.theme_mode.is_dark;
+                let name = format!("{}.ron", if is_dark { fl!("dark") } else { fl!("light") });
+                select_export_file(name, Message::ExportFile)
+            }
+            Message::ExportGtkCss => {
+                let css = gtk_css::css(&self.theme_builder.clone().build());
+
+                Command::perform(
+                    async move { tokio::task::spawn_blocking(move || gtk_css::write(&css)).await },
+                    |res| {
+                        let message = match res {
+                            Ok(Ok(())) => Message::ExportSuccess(None),
+                            Ok(Err(why)) => {
+                                tracing::error!(?why, "failed to write the GTK stylesheet");
+                                Message::ExportError
+                            }
+                            Err(why) => {
+                                tracing::error!(?why, "failed to write the GTK stylesheet");
+                                Message::ExportError
+                            }
+                        };
+
+                        crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+                    },
+                )
+            }
+            Message::TerminalFormat(format) => {
+                self.terminal_format = format;
+                Command::none()
+            }
+            Message::ExportTerminalPalette(format) => {
+                self.terminal_format = format;
+                select_export_file(format.file_name().into(), Message::TerminalPaletteFile)
+            }
+            Message::TerminalPaletteFile(f) => {
+                let Some(path) = selected_path(&f) else {
+                    return Command::none();
+                };
+
+                let contents = self.terminal_format.serialize(&self.theme_builder);
+                self.export(path, contents, false)
+            }
+            Message::StartExportBundle => {
+                select_export_file(format!("{}.ron", fl!("theme")), Message::ExportBundleFile)
+            }
+            Message::ShowThemeQr => {
+                let Some(handle) = qr::theme_code(&self.theme_builder)
+                    .as_deref()
+                    .and_then(qr::render)
+                else {
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "theme-qr",
+                        "too-large"
+                    )));
+                };
+
+                self.theme_qr = Some(handle);
+                self.context_view = Some(ContextView::ThemeQr);
+                cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                    fl!("theme-qr").into(),
+                ))
+            }
+            Message::PreviewSampleApp(kind) => {
+                let opened = matches!(self.context_view, Some(ContextView::SampleApp(_)));
+                self.context_view = Some(ContextView::SampleApp(kind));
+
+                if opened {
+                    Command::none()
+                } else {
+                    cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                        fl!("sample-app").into(),
+                    ))
+                }
+            }
+            Message::ExportDiagnostics => {
+                select_export_file("cosmic-appearance.ron".into(), Message::DiagnosticsFile)
+            }
+            Message::ImportFile(f) => {
+                let Some(path) = selected_path(&f) else {
+                    return Command::none();
+                };
+                import_file(path)
+            }
+            Message::ImportRecent(index) => {
+                let Some(path) = self.config.recent_theme_files().get(index).cloned() else {
+                    return Command::none();
+                };
+
+                if !path.exists() {
+                    self.load_recent_theme_files();
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "recent-themes",
+                        "missing"
+                    )));
+                }
+
+                import_file(path)
+            }
+            Message::FileHovered(hovering) => {
+                self.file_hovering = hovering && !self.read_only;
+                Command::none()
+            }
+            Message::FileDropped(path) => {
+                self.file_hovering = false;
+
+                if self.read_only {
+                    return Command::none();
+                }
+
+                if path
+                    .extension()
+                    .map_or(true, |extension| extension != "ron")
+                {
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "import-theme",
+                        "not-theme-file"
+                    )));
+                }
+
+                import_file(path)
+            }
+            Message::IconThemeImportFile(f) => {
+                let Some(path) = selected_path(&f) else {
+                    return Command::none();
+                };
+                Command::perform(
+                    async move { tokio::fs::read_to_string(path).await },
+                    |res| {
+                        let message = if let Some(bundle) = res
+                            .ok()
+                            .and_then(|s| ron::de::from_str::<ThemeBundle>(&s).ok())
+                        {
+                            Message::ImportIconTheme(bundle.icon_theme)
+                        } else {
+                            tracing::error!("failed to import an icon theme from a theme file.");
+                            Message::ImportError(import::Error::Unreadable)
+                        };
+
+                        crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+                    },
+                )
+            }
+            Message::ImportIconTheme(icon_theme) => {
+                let Some(icon_theme) = icon_theme else {
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "icon-theme-import",
+                        "none"
+                    )));
+                };
+
+                match self.icon_themes.iter().position(|t| t.id == icon_theme) {
+                    Some(id) => self.update_untracked(Message::IconTheme(id)),
+                    // The theme may have been installed since the last scan, so it is looked
+                    // for again before giving up.
+                    None => {
+                        self.pending_icon_theme = Some(icon_theme);
+                        self.rescan_icon_themes()
+                    }
+                }
+            }
+            Message::ExportFile(f) => {
+                let Some(path) = selected_path(&f) else {
+                    return Command::none();
+                };
+                let Ok(builder) = ron::ser::to_string_pretty(
+                    &VersionedTheme::current(&self.theme_builder, &self.tk.icon_theme),
+                    PrettyConfig::default(),
+                ) else {
+                    return Command::none();
+                };
+
+                self.export(path, builder, true)
+            }
+            Message::ExportToClipboard => {
+                let Ok(theme) = ron::ser::to_string_pretty(
+                    &VersionedTheme::current(&self.theme_builder, &self.tk.icon_theme),
+                    PrettyConfig::default(),
+                ) else {
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "export-theme",
+                        "failed"
+                    )));
+                };
+
+                Command::batch(vec![
+                    cosmic::iced::clipboard::write(theme),
+                    cosmic::command::message(app::Message::Toast(fl!("export-theme", "success"))),
+                ])
+            }
+            Message::ExportBundleFile(f) => {
+                let Some(path) = selected_path(&f) else {
+                    return Command::none();
+                };
+
+                let Snapshot {
+                    light, dark, tk, ..
+                } = self.snapshot();
+
+                let bundle = ThemeBundle::new(light, dark, Some(tk.icon_theme));
+
+                let Ok(bundle) = ron::ser::to_string_pretty(&bundle, PrettyConfig::default())
+                else {
+                    return Command::none();
+                };
+
+                self.export(path, bundle, true)
+            }
+            Message::ConfirmOverwrite => {
+                let Some((path, contents, recent)) = self.pending_export.take() else {
+                    return Command::none();
+                };
+
+                Command::batch(vec![
+                    cosmic::command::message(crate::app::Message::CloseContextDrawer),
+                    write_export_file(path, contents, recent),
+                ])
+            }
+            Message::CancelOverwrite => {
+                self.pending_export = None;
+                cosmic::command::message(crate::app::Message::CloseContextDrawer)
+            }
+            Message::StartRestoreAllDefaults => {
+                self.context_view = Some(ContextView::ConfirmRestoreDefaults);
+                cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                    fl!("restore-defaults").into(),
+                ))
+            }
+            Message::CancelRestoreDefaults => {
+                cosmic::command::message(crate::app::Message::CloseContextDrawer)
+            }
+            Message::RestoreAllDefaults => self.restore_all_defaults(),
+            Message::OverwriteWithoutConfirm(enabled) => {
+                if let Err(why) = self.config.set_overwrite_without_confirm(enabled) {
+                    tracing::error!(?why, "failed to store overwrite confirmation preference");
+                }
+                Command::none()
+            }
+            Message::DiagnosticsFile(f) => {
+                let Some(path) = selected_path(&f) else {
+                    return Command::none();
+                };
+                let diagnostics = Diagnostics {
+                    theme_mode: &self.theme_mode,
+                    theme_builder: &self.theme_builder,
+                    icon_theme: &self.tk.icon_theme,
+                    toolkit: &self.tk,
+                    environment: Diagnostics::ENVIRONMENT
+                        .iter()
+                        .map(|&var| (var, std::env::var(var).ok()))
+                        .collect(),
+                };
+                let Ok(diagnostics) =
+                    ron::ser::to_string_pretty(&diagnostics, PrettyConfig::default())
+                else {
+                    return Command::none();
+                };
+                self.export(path, diagnostics, false)
+            }
+            Message::FileChooserClosed => Command::none(),
+            Message::ExportError => {
+                cosmic::command::message(app::Message::Toast(fl!("export-theme", "failed")))
+            }
+            Message::ImportError(why) => {
+                cosmic::command::message(app::Message::Toast(why.message()))
+            }
+            Message::ExportSuccess(path) => {
+                tracing::trace!("Export successful");
+                if let Some(path) = path {
+                    self.add_recent_theme_file(path);
+                }
+
+                cosmic::command::message(app::Message::Toast(fl!("export-theme", "success")))
+            }
+            Message::ImportLoaded(theme, path) => {
+                if let Some(path) = path {
+                    self.add_recent_theme_file(path);
+                }
+
+                self.pending_import = Some(theme);
+                self.import_scale = import::DEFAULT_SCALE;
+                self.context_view = Some(ContextView::ImportPreview);
+                Command::batch(vec![
+                    cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                        fl!("import-scale").into(),
+                    )),
+                    self.preview_import(),
+                ])
+            }
+            Message::ImportScale(scale) => {
+                self.import_scale = scale;
+                self.preview_import()
+            }
+            Message::ConfirmImport => {
+                let Some(theme) = self.pending_import.take() else {
+                    return Command::none();
+                };
+
+                let icon_theme = theme.icon_theme().map(String::from);
+                let message = match theme.scaled(import::SCALES[self.import_scale]) {
+                    ImportedTheme::Single(builder, _) => Message::ImportSuccess(builder),
+                    ImportedTheme::Bundle(bundle) => Message::ImportBundleSuccess(bundle),
+                };
+
+                // A missing icon theme keeps the current one, and is reported in a toast.
+                let icon_cmd = match icon_theme {
+                    Some(icon_theme) if icon_theme != self.tk.icon_theme => {
+                        self.update_untracked(Message::ImportIconTheme(Some(icon_theme)))
+                    }
+                    _ => Command::none(),
+                };
+
+                Command::batch(vec![
+                    cosmic::command::message(crate::app::Message::CloseContextDrawer),
+                    cosmic::command::message(app::Message::SetTheme(self.session_theme())),
+                    self.update_untracked(message),
+                    icon_cmd,
+                ])
+            }
+            Message::CancelImport => {
+                self.pending_import = None;
+                Command::batch(vec![
+                    cosmic::command::message(crate::app::Message::CloseContextDrawer),
+                    cosmic::command::message(app::Message::SetTheme(self.session_theme())),
+                ])
+            }
+            Message::ImportSuccess(builder) => {
+                tracing::trace!("Import successful");
+                let valid = theme_is_valid(&builder.clone().build());
+                self.theme_builder = *builder;
+
+                let cmd = self.write_theme();
+
+                self.reload_theme_mode();
+
+                // An invalid theme is reported by `write_theme` instead.
+                if valid && !self.read_only {
+                    Command::batch(vec![
+                        cmd,
+                        cosmic::command::message(app::Message::Toast(fl!(
+                            "import-theme",
+                            "success"
+                        ))),
+                    ])
+                } else {
+                    cmd
+                }
+            }
+            Message::ImportBundleSuccess(bundle) => {
+                tracing::trace!("Import successful");
+                let (light, dark) = bundle.into_builders();
+
+                // Only the active mode can be previewed.
+                if self.preview.is_some() {
+                    self.theme_builder = if self.theme_mode.is_dark { dark } else { light };
+                    let cmd = self.write_theme();
+                    self.reload_theme_mode();
+                    return cmd;
+                }
+
+                // Both modes are validated before writing, and the light theme is restored if
+                // the dark theme fails to be written, so that a bundle is never half applied.
+                let cmd = if theme_is_valid(&light.clone().build())
+                    && theme_is_valid(&dark.clone().build())
+                {
+                    let (_, previous_light) = load_theme_builder(false);
+                    let written = write_theme_builder(&light, false).and_then(|()| {
+                        write_theme_builder(&dark, true).map_err(|why| {
+                            if let Err(why) = write_theme_builder(&previous_light, false) {
+                                tracing::error!(?why, "failed to restore the light theme");
+                            }
+                            why
+                        })
+                    });
+
+                    match written {
+                        Ok(()) => cosmic::command::message(app::Message::Toast(fl!(
+                            "import-theme",
+                            "success"
+                        ))),
+                        Err(ThemeWriteError::Config(why)) => self.set_read_only(&why),
+                        Err(ThemeWriteError::Invalid) => Command::none(),
+                    }
+                } else {
+                    tracing::error!("Refusing to write an invalid theme.");
+                    cosmic::command::message(app::Message::Toast(fl!("theme-invalid")))
+                };
+
+                self.reload_theme_mode();
+                cmd
+            }
+            Message::WindowHintOpacity(opacity) => {
+                self.window_hint_opacity = opacity;
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+            Message::ApplicationOpacity(opacity) => {
+                self.application_opacity = opacity;
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+            Message::ContainerOpacity(opacity) => {
+                self.container_opacity = opacity;
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+            Message::UseDefaultWindowHint(v) => {
+                self.no_custom_window_hint = v;
+                self.theme_builder_needs_update = true;
+                let theme = if self.theme_mode.is_dark {
+                    Theme::dark_default()
+                } else {
+                    Theme::light_default()
+                };
+                if !v {
+                    let window_hint = self
+                        .theme_builder
+                        .window_hint
+                        .filter(|c| !is_palette_accent(*c, &theme))
+                        .unwrap_or(
+                            self.custom_accent
+                                .get_applied_color()
+                                .unwrap_or_default()
+                                .into(),
+                        );
+                    _ = self.accent_window_hint.update::<app::Message>(
+                        ColorPickerUpdate::ActiveColor(Hsv::from_color(window_hint)),
+                    );
+                };
+                Command::none()
+            }
+            Message::ApplyThemeGlobal(enabled) if self.preview.is_some() => {
+                self.tk.apply_theme_global = enabled;
+                Command::none()
+            }
+            Message::ApplyThemeGlobal(enabled) => {
+                if let Some(tk_config) = self.tk_config.as_ref() {
+                    _ = self.tk.set_apply_theme_global(tk_config, enabled);
+                } else {
+                    tracing::error!("Failed to apply theme to GNOME config because the CosmicTK config does not exist.");
+                }
+
+                if enabled {
+                    gnome_color_scheme_command(self.theme_mode.is_dark)
+                } else {
+                    Command::none()
+                }
+            }
+            Message::ExperimentalContextDrawer => {
+                self.context_view = Some(ContextView::Experimental);
+                Command::batch(vec![
+                    cosmic::command::message(crate::app::Message::OpenContextDrawer("".into())),
+                    self.load_snapshot_thumbnails(),
+                ])
+            }
+            Message::SnapshotThumbnail(id, thumbnail) => {
+                if let Some(thumbnail) = thumbnail {
+                    self.snapshot_thumbnails.insert(id, thumbnail);
+                } else {
+                    tracing::error!(id, "failed to generate a snapshot thumbnail");
+                }
+                Command::none()
+            }
+            Message::AnimationTick => {
+                let Some(animation) = self.roundness_animation.as_ref() else {
+                    return Command::none();
+                };
+
+                if animation.progress() >= 1.0 {
+                    self.roundness_animation = None;
+                    return cosmic::command::message(app::Message::SetTheme(self.session_theme()));
+                }
+
+                // Only the preview is animated; the config already holds the new radii.
+                let mut builder = self.theme_builder.clone();
+                builder.corner_radii = animation.radii();
+                cosmic::command::message(app::Message::SetTheme(cosmic::theme::Theme::custom(
+                    Arc::new(builder.build()),
+                )))
+            }
+            Message::ToggleColorLock(role) => {
+                if !self.locked_colors.remove(&role) {
+                    self.locked_colors.insert(role);
+                }
+                Command::none()
+            }
+            Message::RandomizeColors => {
+                let is_dark = self.theme_mode.is_dark;
+                let unlocked = |role| !self.locked_colors.contains(&role);
+
+                if unlocked(ColorRole::Accent) {
+                    let accent = random::accent();
+                    set_picker_color(&mut self.custom_accent, accent);
+                    self.theme_builder.accent = Some(accent);
+                }
+
+                if unlocked(ColorRole::ApplicationBackground) {
+                    set_picker_color(
+                        &mut self.application_background,
+                        random::background(is_dark),
+                    );
+                }
+
+                if unlocked(ColorRole::ContainerBackground) {
+                    set_picker_color(&mut self.container_background, random::background(is_dark));
+                }
+
+                if unlocked(ColorRole::InterfaceText) {
+                    set_picker_color(&mut self.interface_text, random::tint());
+                }
+
+                if unlocked(ColorRole::ControlComponent) {
+                    set_picker_color(&mut self.control_component, random::tint());
+                }
+
+                if unlocked(ColorRole::WindowHint) && !self.no_custom_window_hint {
+                    set_picker_color(&mut self.accent_window_hint, random::accent());
+                }
+
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+            Message::RandomizeAccent => {
+                let accent = random::accent_unlike(self.theme_builder.accent);
+                self.update_untracked(Message::PaletteAccent(Srgba::from_color(accent).into()))
+            }
+            Message::AccentFromWallpaper => {
+                let Some(path) = wallpaper_accent::current_wallpaper() else {
+                    return cosmic::command::message(app::Message::Toast(fl!(
+                        "accent-palette",
+                        "no-wallpaper"
+                    )));
+                };
+
+                if self
+                    .wallpaper_accents
+                    .as_ref()
+                    .is_some_and(|(cached, _)| *cached == path)
+                {
+                    return Command::none();
+                }
+
+                Command::perform(
+                    async move {
+                        let accents = tokio::task::spawn_blocking({
+                            let path = path.clone();
+                            move || wallpaper_accent::extract(&path)
+                        })
+                        .await
+                        .unwrap_or_default();
+
+                        (path, accents)
+                    },
+                    |(path, accents)| {
+                        crate::Message::PageMessage(crate::pages::Message::Appearance(
+                            Message::WallpaperAccents(path, accents),
+                        ))
+                    },
+                )
+            }
+            Message::WallpaperAccents(path, accents) => {
+                self.wallpaper_accents = Some((path, accents));
+                Command::none()
+            }
+            Message::SeedColor(u) => {
+                let finished = matches!(
+                    u,
+                    ColorPickerUpdate::AppliedColor | ColorPickerUpdate::ActionFinished
+                );
+
+                let cmd = match u {
+                    ColorPickerUpdate::ToggleColorPicker => {
+                        self.context_view = Some(ContextView::SeedColor);
+                        self.seed_palette = None;
+                        cosmic::command::message(crate::app::Message::OpenContextDrawer(
+                            fl!("seed-color").into(),
+                        ))
+                    }
+                    ColorPickerUpdate::Cancel => {
+                        cosmic::command::message(crate::app::Message::CloseContextDrawer)
+                    }
+                    _ => Command::none(),
+                };
+
+                let cmd2 = self.seed_color.update::<app::Message>(u);
+
+                match self.seed_color.get_applied_color() {
+                    Some(color) if finished => Command::batch(vec![
+                        cmd,
+                        cmd2,
+                        self.update_untracked(Message::GenerateFromSeed(color)),
+                    ]),
+                    _ => Command::batch(vec![cmd, cmd2]),
+                }
+            }
+            Message::GenerateFromSeed(color) => {
+                self.seed_palette =
+                    Some(SeedPalette::new(Srgb::from(color), self.theme_mode.is_dark));
+                Command::none()
+            }
+            Message::ApplySeedPalette => {
+                let Some(palette) = self.seed_palette.take() else {
+                    return Command::none();
+                };
+
+                set_picker_color(&mut self.custom_accent, palette.accent);
+                self.theme_builder.accent = Some(palette.accent);
+                set_picker_color(&mut self.application_background, palette.background);
+                set_picker_color(&mut self.container_background, palette.container);
+                set_picker_color(&mut self.control_component, palette.neutral);
+                set_picker_color(&mut self.interface_text, palette.text);
+
+                self.theme_builder_needs_update = true;
+                cosmic::command::message(crate::app::Message::CloseContextDrawer)
+            }
+            Message::CopyColorsFromOtherMode => {
+                let (_, other) = load_theme_builder(!self.theme_mode.is_dark);
+
+                self.theme_builder.accent = other.accent;
+                let custom_accent = other.accent.filter(|accent| {
+                    !is_palette_accent(*accent, &self.theme_builder.clone().build())
+                });
+                self.set_or_reset_color(ContextView::CustomAccent, custom_accent);
+                self.set_or_reset_color(ContextView::InterfaceText, other.text_tint);
+                self.set_or_reset_color(ContextView::ControlComponent, other.neutral_tint);
+
+                // Backgrounds made for the other mode often leave text unreadable in this
+                // one, so they are only copied if text on them meets the contrast standard.
+                let mut candidate = self.theme_builder.clone();
+                candidate.text_tint = other.text_tint;
+                candidate.neutral_tint = other.neutral_tint;
+                candidate.bg_color = other.bg_color;
+                candidate.primary_container_bg = other.primary_container_bg;
+                let theme = candidate.build();
+                let ratio = contrast_ratio(theme.background.on.color, theme.background.base.color);
+
+                let cmd = if self.config.contrast_standard().passes(ratio) {
+                    self.set_or_reset_color(
+                        ContextView::ApplicationBackground,
+                        other.bg_color.map(|color| color.color),
+                    );
+                    self.set_or_reset_color(
+                        ContextView::ContainerBackground,
+                        other.primary_container_bg.map(|color| color.color),
+                    );
+                    Command::none()
+                } else {
+                    cosmic::command::message(crate::app::Message::Toast(fl!(
+                        "copy-colors",
+                        "backgrounds-skipped"
+                    )))
+                };
+
+                self.theme_builder_needs_update = true;
+                cmd
+            }
+            Message::TakeSnapshot => {
+                if let Err(why) = snapshot::save(&self.snapshot()) {
+                    tracing::error!(?why, "failed to take an appearance snapshot");
+                }
+                self.load_snapshots();
+                self.load_snapshot_thumbnails()
+            }
+            Message::IconThemeFilter(filter) => {
+                self.icon_theme_filter = filter;
+                Command::none()
+            }
+            Message::LookProfileName(name) => {
+                self.look_profile_name = name;
+                Command::none()
+            }
+            Message::SaveLookProfile(name) => {
+                if let Err(why) = profile::save(&name, &self.look_profile()) {
+                    tracing::error!(?why, name, "failed to save a look profile");
+                    return Command::none();
+                }
+
+                if let Err(why) = self.config.set_active_look_profile(Some(name)) {
+                    tracing::error!(?why, "failed to set the active look profile");
+                }
+
+                self.look_profile_name.clear();
+                self.look_profiles = profile::list();
+                Command::none()
+            }
+            Message::DeleteLookProfile(name) => {
+                if let Err(why) = profile::delete(&name) {
+                    tracing::error!(?why, name, "failed to delete a look profile");
+                    return Command::none();
+                }
+
+                if self.config.active_look_profile() == Some(name.as_str()) {
+                    if let Err(why) = self.config.set_active_look_profile(None) {
+                        tracing::error!(?why, "failed to clear the active look profile");
+                    }
+                }
+
+                self.look_profiles = profile::list();
+                Command::none()
+            }
+            Message::LoadLookProfile(name) => {
+                let profile = match profile::load(&name) {
+                    Ok(profile) => profile,
+                    Err(why) => {
+                        tracing::error!(?why, name, "failed to load a look profile");
+                        return Command::none();
+                    }
+                };
+
+                // Keep the current state, so that switching profiles can be reverted.
+                if let Err(why) = snapshot::save(&self.snapshot()) {
+                    tracing::error!(?why, "failed to take an appearance snapshot");
+                }
+
+                if let Err(why) = self.config.set_active_look_profile(Some(name)) {
+                    tracing::error!(?why, "failed to set the active look profile");
+                }
+
+                self.restore_look_profile(profile)
+            }
+            Message::RestoreSnapshot(id) => {
+                let snapshot = match snapshot::load(id) {
+                    Ok(snapshot) => snapshot,
+                    Err(why) => {
+                        tracing::error!(?why, "failed to load an appearance snapshot");
+                        return Command::none();
+                    }
+                };
+
+                // Keep the current state, so that restoring can itself be reverted.
+                if let Err(why) = snapshot::save(&self.snapshot()) {
+                    tracing::error!(?why, "failed to take an appearance snapshot");
+                }
+
+                self.restore_snapshot(snapshot)
+            }
+            Message::DecorationAccentOnly(enabled) => {
+                if let Err(why) = self.config.set_decoration_accent_only(enabled) {
+                    tracing::error!(?why, "failed to store decoration accent setting");
+                }
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+            Message::Daytime(day_time) => {
+                self.day_time = day_time;
+                self.location_available = true;
+                self.follow_schedule()
+            }
+            Message::ManualLatitude(latitude) => {
+                self.latitude_input = latitude;
+                self.update_manual_location()
+            }
+            Message::ManualLongitude(longitude) => {
+                self.longitude_input = longitude;
+                self.update_manual_location()
+            }
+            Message::ContrastStandard(standard) => {
+                if let Err(why) = self.config.set_contrast_standard(standard) {
+                    tracing::error!(?why, "failed to store contrast standard");
+                }
+                Command::none()
+            }
+            Message::ColorVision(mode) => {
+                self.color_vision = mode;
+                Command::none()
+            }
+            Message::RecentColor(color) => {
+                let Some(on_update) = self
+                    .context_view
+                    .and_then(ContextView::color_picker_message)
+                else {
+                    return Command::none();
+                };
+
+                let cmd = self.update_untracked(on_update(ColorPickerUpdate::ActiveColor(
+                    Hsv::from_color(color),
+                )));
+                Command::batch(vec![
+                    cmd,
+                    self.update_untracked(on_update(ColorPickerUpdate::AppliedColor)),
+                ])
+            }
+            Message::CopyFormat(format) => {
+                self.copy_format = format;
+                Command::none()
+            }
+            Message::CopyColor => {
+                let Some(color) = self.edited_color() else {
+                    return Command::none();
+                };
+
+                Command::batch(vec![
+                    cosmic::iced::clipboard::write(self.copy_format.format(color)),
+                    cosmic::command::message(app::Message::Toast(fl!("copied-to-clipboard"))),
+                ])
+            }
+            Message::ColorInputMode(mode) => {
+                self.color_input_mode = mode;
+                self.color_input_drafts = None;
+                Command::none()
+            }
+            Message::ColorInput(component, input) => {
+                let mode = self.color_input_mode;
+                let current = self.edited_color().map(|color| mode.components(color));
+                let drafts = self
+                    .color_input_drafts
+                    .get_or_insert_with(|| current.unwrap_or_default());
+                drafts[component] = input;
+                Command::none()
+            }
+            Message::ColorInputSubmit => {
+                // Components which are not numbers are discarded.
+                let Some(color) = self
+                    .color_input_drafts
+                    .take()
+                    .and_then(|drafts| self.color_input_mode.color(&drafts))
+                else {
+                    return Command::none();
+                };
+
+                let Some(on_update) = self
+                    .context_view
+                    .and_then(ContextView::color_picker_message)
+                else {
+                    return Command::none();
+                };
+
+                self.update_untracked(on_update(ColorPickerUpdate::ActiveColor(Hsv::from_color(
+                    color,
+                ))))
+            }
+            Message::ClearRecentColors => {
+                if let Some(kind) = self.context_view.and_then(ContextView::recent_colors_kind) {
+                    if let Err(why) = self.config.clear_recent_colors(kind) {
+                        tracing::error!(?why, "failed to clear the recent colors");
+                    }
+                }
+                Command::none()
+            }
+        };
+
+        if let Some(context_view) = self.applied_color_picker.take() {
+            self.remember_applied_color(context_view);
+        }
+
+        if self.theme_builder_needs_update {
+            let mut theme_builder = std::mem::take(&mut self.theme_builder);
+            let application_color = match self.background_gradient() {
+                // Themes only hold solid colors, so applications are given the average.
+                Some(gradient) => Some(gradient.average()),
+                None => self
+                    .application_background
+                    .get_applied_color()
+                    .map(Srgb::from),
+            };
+            theme_builder.bg_color = with_opacity(
+                application_color,
+                || self.application_color(),
+                self.application_opacity,
+            );
+            theme_builder.primary_container_bg = with_opacity(
+                self.container_background
+                    .get_applied_color()
+                    .map(Srgb::from),
+                || self.container_color(),
+                self.container_opacity,
+            );
+            theme_builder.text_tint = self.interface_text.get_applied_color().map(Srgb::from);
+            theme_builder.neutral_tint = self.control_component.get_applied_color().map(Srgb::from);
+            let window_hint = self.accent_window_hint.get_applied_color().map(Srgb::from);
+            theme_builder.window_hint = if self.no_custom_window_hint {
+                None
+            } else {
+                window_hint.map(|hint| self.blended_window_hint(hint))
+            };
+
+            self.theme_builder = theme_builder;
+
+            // Without a writable config, the theme is still edited and previewed.
+            if self.theme_builder_config.is_some() && !self.read_only {
+                let cmd = self.schedule_theme_write();
+                ret = Command::batch(vec![ret, cmd]);
+            }
+        }
+
+        self.live_preview.update(&self.theme_builder);
+        self.update_resettable();
+
+        if needs_sync && self.preview.is_none() && self.config.sync_layout() {
+            let failed = match self.sync_changes() {
+                // Notified once per visit, rather than on every step of a spin button.
+                Ok(outcome) if outcome.synced && !self.sync_notified => {
+                    self.sync_notified = true;
+                    let message = if self.theme_mode.is_dark {
+                        fl!("layout-synced", "light")
+                    } else {
+                        fl!("layout-synced", "dark")
+                    };
+                    ret = Command::batch(vec![
+                        ret,
+                        cosmic::command::message(app::Message::Toast(message)),
+                    ]);
+                    outcome.failed
+                }
+                Ok(outcome) => outcome.failed,
+                Err(err) => {
+                    tracing::error!(?err, "Error syncing theme changes.");
+                    SyncOutcome::FIELDS
+                }
+            };
+
+            // Failures are also shown once per visit, since they repeat with every edit.
+            if failed > 0 && !self.sync_failure_notified {
+                self.sync_failure_notified = true;
+                ret = Command::batch(vec![
+                    ret,
+                    cosmic::command::message(app::Message::Toast(fl!(
+                        "layout-synced",
+                        "failed",
+                        count = failed
+                    ))),
+                ]);
+            }
+        }
+
+        ret
+    }
+
+    /// Builds a theme from the theme builder and writes both to their configs.
+    ///
+    /// Nothing is written if the built theme is invalid, in which case the
+    /// previously written theme is reloaded and an error toast is shown.
+    /// While previewing, the theme is applied to the session instead.
+    fn write_theme(&mut self) -> Command<app::Message> {
+        self.theme_write_pending = false;
+        let builder = self.scoped_theme_builder();
+
+        let mut theme = builder.clone().build();
+        if let Some(color) = self.selection_color.get_applied_color() {
+            set_selection_color(&mut theme, Srgb::from(color));
+        }
+
+        if let Some(preview) = self.preview.as_mut() {
+            if theme_is_valid(&theme) {
+                *preview = self.theme_builder.clone();
+                return cosmic::command::message(app::Message::SetTheme(
+                    cosmic::theme::Theme::custom(Arc::new(theme)),
+                ));
+            }
+        } else {
+            self.store_theme_overrides();
+            match write_theme_config(&builder, theme, self.theme_mode.is_dark) {
+                Ok(()) => return Command::none(),
+                Err(ThemeWriteError::Config(why)) => {
+                    let cmd = self.set_read_only(&why);
+                    self.reload_theme_mode();
+                    return cmd;
+                }
+                Err(ThemeWriteError::Invalid) => (),
+            }
+        }
+
+        tracing::error!("Refusing to write an invalid theme.");
+        self.reload_theme_mode();
+        cosmic::command::message(app::Message::Toast(fl!("theme-invalid")))
+    }
+
+    /// Writes the theme once it stops changing, so that rapid edits such as holding down a
+    /// spin button are written once.
+    fn schedule_theme_write(&mut self) -> Command<app::Message> {
+        self.theme_write_pending = true;
+        self.theme_write_generation = self.theme_write_generation.wrapping_add(1);
+        let generation = self.theme_write_generation;
+
+        Command::perform(tokio::time::sleep(THEME_WRITE_DEBOUNCE), move |()| {
+            crate::Message::PageMessage(crate::pages::Message::Appearance(
+                Message::FlushThemeWrite(generation),
+            ))
+        })
+    }
+
+    /// The theme the session should show when nothing is being animated or imported.
+    fn session_theme(&self) -> cosmic::theme::Theme {
+        match self.preview.as_ref() {
+            Some(builder) => cosmic::theme::Theme::custom(Arc::new(builder.clone().build())),
+            None => cosmic::theme::system_preference(),
+        }
+    }
+
+    /// Applies the pending import at the chosen scale to the session, without writing it.
+    fn preview_import(&self) -> Command<app::Message> {
+        let Some(theme) = self.pending_import.as_ref() else {
+            return Command::none();
+        };
+
+        let theme = theme
+            .scaled(import::SCALES[self.import_scale])
+            .builder(self.theme_mode.is_dark)
+            .build();
+
+        if !theme_is_valid(&theme) {
+            return Command::none();
+        }
+
+        cosmic::command::message(app::Message::SetTheme(cosmic::theme::Theme::custom(
+            Arc::new(theme),
+        )))
+    }
+
+    /// The current appearance state of both color modes.
+    fn snapshot(&self) -> Snapshot {
+        let (_, other) = load_theme_builder(!self.theme_mode.is_dark);
+
+        let (light, dark) = if self.theme_mode.is_dark {
+            (other, self.theme_builder.clone())
+        } else {
+            (self.theme_builder.clone(), other)
+        };
+
+        Snapshot {
+            theme_mode: self.theme_mode,
+            light,
+            dark,
+            tk: self.tk.clone(),
+        }
+    }
+
+    /// The current look, including the settings of other toolkits.
+    fn look_profile(&self) -> LookProfile {
+        let Snapshot {
+            theme_mode,
+            light,
+            dark,
+            tk,
+        } = self.snapshot();
+
+        LookProfile {
+            theme_mode,
+            light,
+            dark,
+            tk,
+            cursor_theme: self
+                .cursor_theme_active
+                .and_then(|id| self.cursor_themes.get(id))
+                .map(|theme| theme.id.clone()),
+            interface_font: self.config.interface_font().map(String::from),
+            monospace_font: self.config.monospace_font().map(String::from),
+            gtk_theme: self.gtk_theme_active.clone(),
+        }
+    }
+
+    /// Restores the COSMIC settings of a look profile, and applies each of its other
+    /// settings as if it were chosen on the page.
+    fn restore_look_profile(&mut self, profile: LookProfile) -> Command<app::Message> {
+        let LookProfile {
+            theme_mode,
+            light,
+            dark,
+            tk,
+            cursor_theme,
+            interface_font,
+            monospace_font,
+            gtk_theme,
+        } = profile;
+
+        let mut commands = vec![self.restore_snapshot(Snapshot {
+            theme_mode,
+            light,
+            dark,
+            tk,
+        })];
+
+        if let Some(cursor_theme) = cursor_theme {
+            match self.cursor_themes.iter().position(|t| t.id == cursor_theme) {
+                Some(id) => commands.push(self.update_untracked(Message::CursorTheme(id))),
+                None => tracing::warn!(cursor_theme, "cursor theme of a look profile is missing"),
+            }
+        }
+
+        if let Some(family) = interface_font {
+            commands.push(self.update_untracked(Message::InterfaceFont(family)));
+        }
+
+        if let Some(family) = monospace_font {
+            commands.push(self.update_untracked(Message::MonospaceFont(family)));
+        }
+
+        if let Some(id) = gtk_theme {
+            commands.push(self.update_untracked(Message::GtkTheme(id)));
+        }
+
+        Command::batch(commands)
+    }
+
+    fn load_snapshots(&mut self) {
+        self.snapshots = snapshot::list()
+            .into_iter()
+            .map(|id| (id, snapshot::timestamp(id)))
+            .collect();
+    }
+
+    /// Returns both color modes, the icon theme, and the panel roundness to how COSMIC
+    /// ships them, along with the settings applied on top of the theme, and forgets saved
+    /// accents, recent colors, and look profiles.
+    ///
+    /// While previewing, only the previewed theme is restored.
+    fn restore_all_defaults(&mut self) -> Command<app::Message> {
+        let is_dark = self.theme_mode.is_dark;
+
+        // Overrides are cleared first, so that they are not applied to the default theme.
+        if self.preview.is_none() {
+            if let Err(why) = self.config.clear_theme_overrides() {
+                tracing::error!(?why, "failed to clear the settings applied to the theme");
+            }
+            self.window_hint_opacity = self.config.window_hint_opacity();
+        }
+
+        self.set_theme_builder(if is_dark {
+            ThemeBuilder::dark()
+        } else {
+            ThemeBuilder::light()
+        });
+        let mut cmds = vec![
+            cosmic::command::message(crate::app::Message::CloseContextDrawer),
+            self.write_theme(),
+        ];
+
+        if self.preview.is_none() {
+            let other = if is_dark {
+                ThemeBuilder::light()
+            } else {
+                ThemeBuilder::dark()
+            };
+            if let Err(why) = write_theme_builder(&other, !is_dark) {
+                tracing::error!(?why, "failed to restore the theme of the other mode");
+            }
+
+            Self::update_panel_radii(Roundness::Round);
+
+            if let Err(why) = self.config.clear_saved_accents() {
+                tracing::error!(?why, "failed to clear saved accents");
+            }
+            if let Err(why) = self.config.clear_all_recent_colors() {
+                tracing::error!(?why, "failed to clear recent colors");
+            }
+
+            for name in profile::list() {
+                if let Err(why) = profile::delete(&name) {
+                    tracing::error!(?why, name, "failed to delete a look profile");
+                }
+            }
+            if let Err(why) = self.config.set_active_look_profile(None) {
+                tracing::error!(?why, "failed to clear the active look profile");
+            }
+            self.look_profiles = profile::list();
+        }
+
+        let icon_theme = CosmicTk::default().icon_theme;
+        match self
+            .icon_themes
+            .iter()
+            .position(|theme| theme.id == icon_theme)
+        {
+            Some(id) => cmds.push(self.update_untracked(Message::IconTheme(id))),
+            None => tracing::error!(icon_theme, "the default icon theme is not installed"),
+        }
+
+        self.reload_theme_mode();
+        Command::batch(cmds)
+    }
+
+    /// Writes an exported file, asking first if it would replace an existing file.
+    ///
+    /// Every kind of export goes through here, since the file chooser does not always ask
+    /// before returning an existing file.
+    ///
+    /// Theme files, as opposed to diagnostics, are remembered as `recent` so that they can
+    /// be imported again.
+    fn export(&mut self, path: PathBuf, contents: String, recent: bool) -> Command<app::Message> {
+        if self.config.overwrite_without_confirm() || !path.exists() {
+            return write_export_file(path, contents, recent);
+        }
+
+        self.pending_export = Some((path, contents, recent));
+        self.context_view = Some(ContextView::ConfirmOverwrite);
+        cosmic::command::message(crate::app::Message::OpenContextDrawer(
+            fl!("confirm-overwrite").into(),
+        ))
+    }
+
+    fn add_recent_theme_file(&mut self, path: PathBuf) {
+        if let Err(why) = self.config.add_recent_theme_file(path) {
+            tracing::error!(?why, "failed to store a recent theme file");
+        }
+
+        self.load_recent_theme_files();
+    }
+
+    /// Forgets recent theme files which no longer exist, and lists the names of the rest.
+    fn load_recent_theme_files(&mut self) {
+        if let Err(why) = self.config.prune_recent_theme_files() {
+            tracing::error!(?why, "failed to prune the recent theme files");
+        }
+
+        self.recent_theme_files = self
+            .config
+            .recent_theme_files()
+            .iter()
+            .map(|path| {
+                path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                )
+            })
+            .collect();
+    }
+
+    /// Generates thumbnails for the snapshots which do not have one yet.
+    fn load_snapshot_thumbnails(&self) -> Command<app::Message> {
+        let missing: Vec<SnapshotId> = self
+            .snapshots
+            .iter()
+            .map(|(id, _)| *id)
+            .filter(|id| !self.snapshot_thumbnails.contains_key(id))
+            .collect();
+
+        if missing.is_empty() {
+            return Command::none();
+        }
+
+        Command::batch(missing.into_iter().map(|id| {
+            Command::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        let snapshot = snapshot::load(id).ok()?;
+                        let builder = if snapshot.theme_mode.is_dark {
+                            snapshot.dark
+                        } else {
+                            snapshot.light
+                        };
+
+                        Some(ThemeThumbnail::new(builder))
+                    })
+                    .await
+                    .ok()
+                    .flatten()
+                },
+                move |thumbnail| {
+                    crate::Message::PageMessage(crate::pages::Message::Appearance(
+                        Message::SnapshotThumbnail(id, thumbnail),
+                    ))
+                },
+            )
+        }))
+    }
+
+    /// Writes every component of a snapshot through its respective config.
+    fn restore_snapshot(&mut self, snapshot: Snapshot) -> Command<app::Message> {
+        // A restored snapshot is always written, even while previewing.
+        self.preview = None;
+
+        for (builder, is_dark) in [(&snapshot.light, false), (&snapshot.dark, true)] {
+            match write_theme_builder(builder, is_dark) {
+                Ok(()) => (),
+                Err(ThemeWriteError::Config(why)) => return self.set_read_only(&why),
+                Err(ThemeWriteError::Invalid) => {
+                    tracing::error!("Refusing to restore an invalid theme.");
+                    return cosmic::command::message(app::Message::Toast(fl!("theme-invalid")));
+                }
+            }
+        }
+
+        if let Some(config) = self.theme_mode_config.as_ref() {
+            if let Err(err) = snapshot.theme_mode.write_entry(config) {
+                tracing::error!(?err, "Error restoring the theme mode");
+            }
+        }
+
+        if let Some(config) = self.tk_config.as_ref() {
+            if let Err(err) = snapshot.tk.write_entry(config) {
+                tracing::error!(?err, "Error restoring the toolkit config");
+            }
+        }
+
+        self.theme_mode = snapshot.theme_mode;
+        self.tk = snapshot.tk;
+        self.reload_theme_mode();
+        self.sort_icon_themes();
+        self.load_snapshots();
+
+        let gnome_cmd = self
+            .icon_theme_active
+            .and_then(|id| self.icon_themes.get(id))
+            .map_or_else(Command::none, |theme| {
+                gnome_icon_theme_command(theme.name.clone())
+            });
+
+        Self::update_panel_radii(self.roundness);
+
+        Command::batch(vec![
+            gnome_cmd,
+            cosmic::command::message(app::Message::SetTheme(cosmic::theme::system_preference())),
+        ])
+    }
+
+    /// Stores the settings which are applied to the theme, but kept outside of it.
+    ///
+    /// While previewing, they are only kept in memory until the preview is committed.
+    fn store_theme_overrides(&mut self) {
+        let decoration_accent = if self.config.decoration_accent_only() {
+            self.theme_builder.accent
+        } else {
+            None
+        };
+        if let Err(why) = self
+            .config
+            .set_decoration_accent(self.theme_mode.is_dark, decoration_accent)
+        {
+            tracing::error!(?why, "failed to store decoration accent");
+        }
+
+        let selection_color = self.selection_color.get_applied_color().map(Srgb::from);
+        if let Err(why) = self
+            .config
+            .set_selection_color(self.theme_mode.is_dark, selection_color)
+        {
+            tracing::error!(?why, "failed to store selection color");
+        }
+
+        let window_hint = self.accent_window_hint.get_applied_color().map(Srgb::from);
+        if let Err(why) = self.config.set_window_hint_color(window_hint) {
+            tracing::error!(?why, "failed to store the window hint color");
+        }
+
+        if let Err(why) = self
+            .config
+            .set_window_hint_opacity(self.window_hint_opacity)
+        {
+            tracing::error!(?why, "failed to store the window hint opacity");
+        }
+    }
+
+    /// Returns to the stored theme, along with the settings kept outside of it.
+    fn discard_preview(&mut self) {
+        self.window_hint_opacity = self.config.window_hint_opacity();
+        self.reload_theme_mode();
+    }
+
+    /// The theme builder to write, with the accent scoped to window decorations if requested.
+    fn scoped_theme_builder(&self) -> ThemeBuilder {
+        let mut builder = self.theme_builder.clone();
+
+        if self.config.decoration_accent_only() {
+            let accent = builder.accent.take();
+            // The window hint follows the accent unless a custom hint is set.
+            builder.window_hint = builder.window_hint.or(accent);
+        }
+
+        builder
+    }
+
+    /// Switches the page into read-only mode after the theme config failed to be written.
+    fn set_read_only(&mut self, why: &cosmic::cosmic_config::Error) -> Command<app::Message> {
+        tracing::error!(?why, "theme config is not writable; disabling edits");
+
+        if self.read_only {
+            return Command::none();
+        }
+
+        self.read_only = true;
+        cosmic::command::message(app::Message::Toast(fl!("theme-read-only")))
+    }
+
+    /// Writes a theme restored from the undo or redo history, and reloads the page from it.
+    fn restore_history(&mut self) -> Command<app::Message> {
+        self.roundness = self.theme_builder.corner_radii.into();
+        let cmd = self.write_theme();
+
+        if self.preview.is_none() {
+            Self::update_panel_radii(self.roundness);
+        }
+
+        self.reload_theme_mode();
+        cmd
+    }
+
+    /// Stores the custom auto-switch times, or `None` to follow sunrise and sunset.
+    fn set_schedule(&mut self, schedule: Option<Schedule>) -> Command<app::Message> {
+        self.auto_switch_schedule = schedule;
+
+        if let Some(config) = self.theme_mode_config.as_ref() {
+            if let Err(why) = Schedule::store(schedule, config) {
+                tracing::error!(?why, "failed to store the auto-switch schedule");
+            }
+        }
+
+        self.follow_schedule()
+    }
+
+    /// The application background gradient of the current theme mode, if one is used.
+    fn background_gradient(&self) -> Option<&BackgroundGradient> {
+        self.config.background_gradient(self.theme_mode.is_dark)
+    }
+
+    fn set_background_gradient(&mut self, gradient: Option<BackgroundGradient>) {
+        if let Err(why) = self
+            .config
+            .set_background_gradient(self.theme_mode.is_dark, gradient)
+        {
+            tracing::error!(?why, "failed to store the application background gradient");
+        }
+
+        self.theme_builder_needs_update = true;
+    }
+
+    /// The application background color chosen in the color picker, or the default
+    /// background of the current theme mode.
+    fn application_color(&self) -> Srgb {
+        self.application_background
+            .get_applied_color()
+            .map(Srgb::from)
+            .unwrap_or_else(|| {
+                let theme = if self.theme_mode.is_dark {
+                    Theme::dark_default()
+                } else {
+                    Theme::light_default()
+                };
+
+                theme.background.base.color
+            })
+    }
+
+    /// The container background color chosen in the color picker, or the default
+    /// container background of the current theme mode.
+    fn container_color(&self) -> Srgb {
+        self.container_background
+            .get_applied_color()
+            .map(Srgb::from)
+            .unwrap_or_else(|| {
+                let theme = if self.theme_mode.is_dark {
+                    Theme::dark_default()
+                } else {
+                    Theme::light_default()
+                };
+
+                theme.primary.base.color
+            })
+    }
+
+    /// Stores the manual location once both coordinates are valid, or clears it once both
+    /// are empty.
+    fn update_manual_location(&mut self) -> Command<app::Message> {
+        let latitude = self.latitude_input.trim();
+        let longitude = self.longitude_input.trim();
+
+        let location = if latitude.is_empty() && longitude.is_empty() {
+            None
+        } else {
+            match (latitude.parse::<f64>(), longitude.parse::<f64>()) {
+                (Ok(latitude), Ok(longitude))
+                    if (-90.0..=90.0).contains(&latitude)
+                        && (-180.0..=180.0).contains(&longitude) =>
+                {
+                    Some((latitude, longitude))
+                }
+                _ => return Command::none(),
+            }
+        };
+
+        if let Err(why) = self.config.set_manual_location(location) {
+            tracing::error!(?why, "failed to store the manual location");
+        }
+
+        self.follow_schedule()
+    }
+
+    /// The times at which auto-switch changes the theme mode, if known by the page.
+    ///
+    /// Custom times take precedence. Otherwise, sunrise and sunset are computed from
+    /// the manual location when location services are unavailable.
+    fn effective_schedule(&self) -> Option<Schedule> {
+        self.auto_switch_schedule.or_else(|| {
+            let (latitude, longitude) = self.config.manual_location()?;
+            (!self.location_available).then(|| Schedule::solar(latitude, longitude))
+        })
+    }
+
+    /// Recomputes whether it is day by the known auto-switch times, and switches the
+    /// theme mode to match when auto-switch is enabled.
+    fn follow_schedule(&mut self) -> Command<app::Message> {
+        let Some(schedule) = self.effective_schedule() else {
+            return Command::none();
+        };
+
+        self.day_time = schedule.is_day();
+
+        if self.theme_mode.auto_switch
+            && self.preview.is_none()
+            && self.theme_mode.is_dark == self.day_time
+        {
+            return self.update(Message::DarkMode(!self.day_time));
+        }
+
+        Command::none()
+    }
+
+    fn auto_switch_context_view(&self) -> Element<'_, crate::pages::Message> {
+        let custom = self.auto_switch_schedule.is_some();
+
+        let time = |time: TimeOfDay, on_change: fn(TimeOfDay) -> Message| {
+            row::with_capacity(3)
+                .push(cosmic::widget::spin_button(
+                    format!("{:02}", time.hour),
+                    move |msg| {
+                        on_change(time.step_hour(matches!(msg, spin_button::Message::Increment)))
+                    },
+                ))
+                .push(text(":"))
+                .push(cosmic::widget::spin_button(
+                    format!("{:02}", time.minute),
+                    move |msg| {
+                        on_change(time.step_minute(matches!(msg, spin_button::Message::Increment)))
+                    },
+                ))
+                .spacing(self.theme_builder.spacing.space_xxs)
+                .align_items(cosmic::iced_core::Alignment::Center)
+        };
+
+        settings::view_section("")
+            .add(settings::item_row(vec![radio(
+                fl!("auto-switch", "sunrise-sunset"),
+                false,
+                Some(custom),
+                Message::AutoSwitchCustom,
+            )
+            .into()]))
+            .add(settings::item_row(vec![radio(
+                fl!("auto-switch", "custom"),
+                true,
+                Some(custom),
+                Message::AutoSwitchCustom,
+            )
+            .into()]))
+            .apply(|section| match self.auto_switch_schedule {
+                Some(schedule) => section
+                    .add(
+                        settings::item::builder(fl!("auto-switch", "light-time"))
+                            .control(time(schedule.light, Message::AutoSwitchLightTime)),
+                    )
+                    .add(
+                        settings::item::builder(fl!("auto-switch", "dark-time"))
+                            .control(time(schedule.dark, Message::AutoSwitchDarkTime)),
+                    ),
+                None => section
+                    .add(
+                        settings::item::builder(fl!("auto-switch", "latitude"))
+                            .description(fl!("auto-switch", "location-desc"))
+                            .control(
+                                cosmic::widget::text_input("", &self.latitude_input)
+                                    .on_input(Message::ManualLatitude)
+                                    .width(Length::Fixed(120.0)),
+                            ),
+                    )
+                    .add(
+                        settings::item::builder(fl!("auto-switch", "longitude")).control(
+                            cosmic::widget::text_input("", &self.longitude_input)
+                                .on_input(Message::ManualLongitude)
+                                .width(Length::Fixed(120.0)),
+                        ),
+                    ),
+            })
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance)
+    }
+
+    /// Rereads every config the page depends on, keeping the icon and other view state.
+    fn reload_configs(&mut self) {
+        (self.theme_mode_config, self.theme_mode) = load_theme_mode();
+        self.auto_switch_schedule = self.theme_mode_config.as_ref().and_then(Schedule::load);
+
+        self.config = PageConfig::new();
+        self.text_scaling = self.config.text_scaling_factor();
+        self.icon_preview_size = self.config.icon_preview_size().unwrap_or(ICON_THUMB_SIZE);
+        self.window_hint_opacity = self.config.window_hint_opacity();
+        (self.latitude_input, self.longitude_input) = self
+            .config
+            .manual_location()
+            .map_or_else(Default::default, |(latitude, longitude)| {
+                (latitude.to_string(), longitude.to_string())
+            });
+
+        // The themes may have been changed elsewhere while the page was not shown.
+        self.undo_history.clear();
+        self.redo_history.clear();
+        self.preview = None;
+        self.read_only = false;
+        self.floating_panel = panel_is_floating();
+        self.reload_theme_mode();
+    }
+
+    /// Reloads the theme of the current mode, or the preview while previewing, without
+    /// touching the state which does not depend on the theme.
+    fn reload_theme_mode(&mut self) {
+        let theme_builder = match self.preview.clone() {
+            Some(builder) => builder,
+            None => {
+                let (theme_builder_config, theme_builder) =
+                    load_theme_builder(self.theme_mode.is_dark);
+                (self.tk_config, self.tk) = load_toolkit();
+                self.theme_builder_config = theme_builder_config;
+                theme_builder
+            }
+        };
+
+        self.set_theme_builder(theme_builder);
+    }
+
+    /// Sets the theme builder, and updates the state which is derived from it.
+    fn set_theme_builder(&mut self, mut theme_builder: ThemeBuilder) {
+        // The written theme keeps the default accent, so restore the one that was chosen.
+        if self.config.decoration_accent_only() && theme_builder.accent.is_none() {
+            theme_builder.accent = self.config.decoration_accent(self.theme_mode.is_dark);
+        }
+
+        let theme = if self.theme_mode.is_dark {
+            Theme::dark_default()
+        } else {
+            Theme::light_default()
+        };
+        let custom_accent = theme_builder
+            .accent
+            .filter(|c| !is_palette_accent(*c, &theme));
+
+        self.roundness = theme_builder.corner_radii.into();
+        self.custom_accent =
+            ColorPickerModel::new(&*HEX, &*RGB, None, custom_accent.map(Color::from));
+        self.application_background = ColorPickerModel::new(
+            &*HEX,
+            &*RGB,
+            Some(theme.background.base.into()),
+            theme_builder.bg_color.map(Color::from),
+        );
+        self.container_background = ColorPickerModel::new(
+            &*HEX,
+            &*RGB,
+            None,
+            theme_builder.primary_container_bg.map(Color::from),
+        );
+        self.interface_text = ColorPickerModel::new(
+            &*HEX,
+            &*RGB,
+            Some(theme.background.on.into()),
+            theme_builder.text_tint.map(Color::from),
+        );
+        self.control_component = ColorPickerModel::new(
+            &*HEX,
+            &*RGB,
+            Some(theme.palette.neutral_5.into()),
+            theme_builder.neutral_tint.map(Color::from),
+        );
+        self.selection_color = ColorPickerModel::new(
+            &*HEX,
+            &*RGB,
+            Some(
+                theme_builder
+                    .accent
+                    .map_or(theme.accent.base.into(), Color::from),
+            ),
+            self.config
+                .selection_color(self.theme_mode.is_dark)
+                .map(Color::from),
+        );
+        // The theme holds the hint blended by its opacity, so the chosen color is restored.
+        let window_hint = match self.config.window_hint_color() {
+            Some(color)
+                if theme_builder.window_hint.is_some()
+                    && self.config.window_hint_opacity() < 1.0 =>
+            {
+                Some(color)
+            }
+            _ => theme_builder.window_hint,
+        };
+        self.accent_window_hint =
+            ColorPickerModel::new(&*HEX, &*RGB, None, window_hint.map(Color::from));
+        self.no_custom_window_hint = theme_builder.accent.is_some();
+        self.application_opacity = theme_builder.bg_color.map_or(1.0, |color| color.alpha);
+        self.container_opacity = theme_builder
+            .primary_container_bg
+            .map_or(1.0, |color| color.alpha);
+        self.live_preview.update(&theme_builder);
+        self.theme_builder = theme_builder;
+        self.update_resettable();
+
+        if self.config.separate_mode_accents() {
+            self.load_mode_accents();
+        }
+    }
+
+    /// Loads the accents of both color modes into the day and night accent pickers.
+    fn load_mode_accents(&mut self) {
+        for is_dark in [false, true] {
+            let accent = if is_dark == self.theme_mode.is_dark {
+                self.theme_builder.accent
+            } else {
+                load_theme_builder(is_dark).1.accent
+            };
+
+            let model = ColorPickerModel::new(&*HEX, &*RGB, None, accent.map(Color::from));
+            if is_dark {
+                self.night_accent = model;
+            } else {
+                self.day_accent = model;
+            }
+        }
+    }
+
+    /// Applies the day or night accent to the theme of its color mode.
+    ///
+    /// The accent of the active mode is edited like any other theme color. The other mode's
+    /// theme is written directly, since accents are never synced between modes.
+    fn update_mode_accent(&mut self, is_dark: bool, u: ColorPickerUpdate) -> Command<app::Message> {
+        let (context_view, title) = if is_dark {
+            (ContextView::NightAccent, fl!("mode-accents", "night"))
+        } else {
+            (ContextView::DayAccent, fl!("mode-accents", "day"))
+        };
+
+        let finished = matches!(
+            u,
+            ColorPickerUpdate::AppliedColor | ColorPickerUpdate::Reset
+        );
+        let cmd = self.update_color_picker(&u, context_view, title.into());
+        let model = if is_dark {
+            &mut self.night_accent
+        } else {
+            &mut self.day_accent
+        };
+        let cmd2 = model.update::<app::Message>(u);
+
+        if !finished {
+            return Command::batch(vec![cmd, cmd2]);
+        }
+
+        let accent = model.get_applied_color().map(Srgb::from);
+
+        if is_dark == self.theme_mode.is_dark {
+            match accent {
+                Some(accent) => set_picker_color(&mut self.custom_accent, accent),
+                None => self.reset_color(ContextView::CustomAccent),
+            }
+
+            self.theme_builder.accent = accent;
+            self.theme_builder_needs_update = true;
+        } else if self.preview.is_none() {
+            let (_, mut builder) = load_theme_builder(is_dark);
+            builder.accent = accent;
+
+            if let Err(why) = write_theme_builder(&builder, is_dark) {
+                tracing::error!(
+                    ?why,
+                    is_dark,
+                    "failed to write the accent of the other mode"
+                );
+            }
+        }
+
+        Command::batch(vec![cmd, cmd2])
+    }
+
+    fn update_color_picker(
+        &mut self,
+        message: &ColorPickerUpdate,
+        context_view: ContextView,
+        context_title: Cow<'static, str>,
+    ) -> Command<app::Message> {
+        match message {
+            ColorPickerUpdate::AppliedColor => {
+                // The picker applies its color after this, so it is remembered once the
+                // message has been handled.
+                self.applied_color_picker = Some(context_view);
+                self.theme_builder_needs_update = true;
+                cosmic::command::message(crate::app::Message::CloseContextDrawer)
+            }
+
+            ColorPickerUpdate::Reset => {
+                self.reset_color(context_view);
+                self.theme_builder_needs_update = true;
+                cosmic::command::message(crate::app::Message::CloseContextDrawer)
+            }
+
+            ColorPickerUpdate::ActionFinished => {
+                self.theme_builder_needs_update = true;
+                Command::none()
+            }
+
+            ColorPickerUpdate::Cancel => {
+                cosmic::command::message(crate::app::Message::CloseContextDrawer)
+            }
+
+            ColorPickerUpdate::ActiveColor(color) => {
+                self.picker_color = Some(Srgb::from_color(*color));
+                self.color_input_drafts = None;
+                Command::none()
+            }
+
+            ColorPickerUpdate::ToggleColorPicker => {
+                self.context_view = Some(context_view);
+                self.picker_color = None;
+                self.color_input_drafts = None;
+                cosmic::command::message(crate::app::Message::OpenContextDrawer(context_title))
+            }
+
+            _ => Command::none(),
+        }
+    }
+
+    /// The color being edited in the color picker shown, or else its applied color.
+    fn edited_color(&self) -> Option<Srgb> {
+        self.picker_color.or_else(|| {
+            self.context_view
+                .and_then(|view| self.color_picker_model(view))
+                .and_then(ColorPickerModel::get_applied_color)
+                .map(Srgb::from)
+        })
+    }
+
+    /// The palette accents, followed by the saved accents and those of the wallpaper,
+    /// in the order of the accent row.
+    fn accent_swatches(&self) -> Vec<AccentSwatch> {
+        let palette = self.theme_builder.palette.as_ref();
+        let named = [
+            (fl!("accent-name", "blue"), palette.accent_blue),
+            (fl!("accent-name", "indigo"), palette.accent_indigo),
+            (fl!("accent-name", "purple"), palette.accent_purple),
+            (fl!("accent-name", "pink"), palette.accent_pink),
+            (fl!("accent-name", "red"), palette.accent_red),
+            (fl!("accent-name", "orange"), palette.accent_orange),
+            (fl!("accent-name", "yellow"), palette.accent_yellow),
+            (fl!("accent-name", "green"), palette.accent_green),
+            (fl!("accent-name", "warm-grey"), palette.accent_warm_grey),
+        ]
+        .into_iter()
+        .map(|(name, color)| AccentSwatch {
+            name,
+            color,
+            saved: None,
+        });
+
+        let saved = self
+            .config
+            .saved_accents()
+            .iter()
+            .enumerate()
+            .map(|(i, color)| AccentSwatch {
+                name: fl!("accent-palette", "saved"),
+                color: Srgba::from(*color),
+                saved: Some(i),
+            });
+
+        let wallpaper = self
+            .wallpaper_accents
+            .iter()
+            .flat_map(|(_, accents)| accents.iter())
+            .map(|color| AccentSwatch {
+                name: fl!("accent-palette", "wallpaper"),
+                color: Srgba::from(*color),
+                saved: None,
+            });
+
+        named.chain(saved).chain(wallpaper).collect()
+    }
+
+    /// The theme restored by a reset: the system default theme of this mode, or the
+    /// high contrast theme while high contrast is enabled.
+    fn default_builder(&self) -> ThemeBuilder {
+        let is_dark = self.theme_mode.is_dark;
+        if is_high_contrast(&self.theme_builder) {
+            return baseline_builder(is_dark, true);
+        }
+
+        let id = if is_dark {
+            DARK_THEME_BUILDER_ID
+        } else {
+            LIGHT_THEME_BUILDER_ID
+        };
+
+        cosmic::cosmic_config::Config::system(id, ThemeBuilder::VERSION).map_or_else(
+            |_| baseline_builder(is_dark, false),
+            |config| match ThemeBuilder::get_entry(&config) {
+                Ok(t) => t,
+                Err((errs, t)) => {
+                    for err in errs {
+                        tracing::warn!(?err, "Error getting system theme builder");
+                    }
+                    t
+                }
+            },
+        )
+    }
+
+    /// Finds the reset scopes which would change the theme.
+    fn update_resettable(&mut self) {
+        let baseline = baseline_builder(
+            self.theme_mode.is_dark,
+            is_high_contrast(&self.theme_builder),
+        );
+
+        self.resettable = ResetScope::ALL
+            .into_iter()
+            .filter(|scope| scope.differs(&self.theme_builder, &baseline))
+            .collect();
+    }
+
+    /// The model of the color picker shown in a context drawer.
+    fn color_picker_model(&self, context_view: ContextView) -> Option<&ColorPickerModel> {
+        match context_view {
+            ContextView::AccentWindowHint => Some(&self.accent_window_hint),
+            ContextView::ApplicationBackground => Some(&self.application_background),
+            ContextView::ContainerBackground => Some(&self.container_background),
+            ContextView::ControlComponent => Some(&self.control_component),
+            ContextView::CustomAccent => Some(&self.custom_accent),
+            ContextView::DayAccent => Some(&self.day_accent),
+            ContextView::NightAccent => Some(&self.night_accent),
+            ContextView::InterfaceText => Some(&self.interface_text),
+            ContextView::SeedColor => Some(&self.seed_color),
+            ContextView::SelectionColor => Some(&self.selection_color),
+            _ => None,
+        }
+    }
+
+    /// Adds the applied color of a color picker to the recent colors of its kind.
+    fn remember_applied_color(&mut self, context_view: ContextView) {
+        let Some(kind) = context_view.recent_colors_kind() else {
+            return;
+        };
+
+        let Some(color) = self
+            .color_picker_model(context_view)
+            .and_then(ColorPickerModel::get_applied_color)
+        else {
+            return;
+        };
+
+        if let Err(why) = self.config.add_recent_color(kind, Srgb::from(color)) {
+            tracing::error!(?why, "failed to store a recent color");
+        }
+    }
+
+    /// Sets the color of a color picker, or clears it when there is no color.
+    fn set_or_reset_color(&mut self, context_view: ContextView, color: Option<Srgb>) {
+        let Some(color) = color else {
+            self.reset_color(context_view);
+            return;
+        };
+
+        let model = match context_view {
+            ContextView::ApplicationBackground => &mut self.application_background,
+            ContextView::ContainerBackground => &mut self.container_background,
+            ContextView::ControlComponent => &mut self.control_component,
+            ContextView::CustomAccent => &mut self.custom_accent,
+            ContextView::InterfaceText => &mut self.interface_text,
+            _ => return,
+        };
+
+        set_picker_color(model, color);
+    }
+
+    /// Clears the color of a color picker, so that its part of the theme reverts to the
+    /// automatic color while the rest of the theme is kept.
+    ///
+    /// The theme follows the picker's applied color when it is next rebuilt.
+    fn reset_color(&mut self, context_view: ContextView) {
+        let theme = if self.theme_mode.is_dark {
+            Theme::dark_default()
+        } else {
+            Theme::light_default()
+        };
+
+        let (model, fallback) = match context_view {
+            ContextView::AccentWindowHint => (&mut self.accent_window_hint, None),
+            ContextView::ApplicationBackground => (
+                &mut self.application_background,
+                Some(theme.background.base.into()),
+            ),
+            ContextView::ContainerBackground => (&mut self.container_background, None),
+            ContextView::ControlComponent => (
+                &mut self.control_component,
+                Some(theme.palette.neutral_5.into()),
+            ),
+            ContextView::CustomAccent => (&mut self.custom_accent, None),
+            ContextView::DayAccent => (&mut self.day_accent, None),
+            ContextView::NightAccent => (&mut self.night_accent, None),
+            ContextView::InterfaceText => {
+                (&mut self.interface_text, Some(theme.background.on.into()))
+            }
+            ContextView::SelectionColor => (
+                &mut self.selection_color,
+                Some(
+                    self.theme_builder
+                        .accent
+                        .map_or(theme.accent.base.into(), Color::from),
+                ),
+            ),
+            _ => return,
+        };
+
+        *model = ColorPickerModel::new(&*HEX, &*RGB, fallback, None);
+    }
+
+    fn update_panel_radii(roundness: Roundness) {
+        let panel_config_helper = CosmicPanelConfig::cosmic_config("Panel").ok();
+        let dock_config_helper = CosmicPanelConfig::cosmic_config("Dock").ok();
+        let mut panel_config = panel_config_helper.as_ref().and_then(|config_helper| {
+            let panel_config = CosmicPanelConfig::get_entry(config_helper).ok()?;
+            (panel_config.name == "Panel").then_some(panel_config)
+        });
+        let mut dock_config = dock_config_helper.as_ref().and_then(|config_helper| {
+            let panel_config = CosmicPanelConfig::get_entry(config_helper).ok()?;
+            (panel_config.name == "Dock").then_some(panel_config)
+        });
+
+        if let Some(panel_config_helper) = panel_config_helper.as_ref() {
+            if let Some(panel_config) = panel_config.as_mut() {
+                let radii = if panel_config.anchor_gap || !panel_config.expand_to_edges {
+                    panel_radius(roundness)
+                } else {
+                    0
+                };
+                let update = panel_config.set_border_radius(panel_config_helper, radii);
+                if let Err(err) = update {
+                    tracing::error!(?err, "Error updating panel corner radii");
+                }
+            }
+        };
+
+        if let Some(dock_config_helper) = dock_config_helper.as_ref() {
+            if let Some(dock_config) = dock_config.as_mut() {
+                let radii = if dock_config.anchor_gap || !dock_config.expand_to_edges {
+                    panel_radius(roundness)
+                } else {
+                    0
+                };
+                let update = dock_config.set_border_radius(dock_config_helper, radii);
+                if let Err(err) = update {
+                    tracing::error!(?err, "Error updating dock corner radii");
+                }
+            }
+        };
+    }
+}
+
+impl page::Page<crate::pages::Message> for Page {
+    fn content(
+        &self,
+        sections: &mut SlotMap<section::Entity, Section<crate::pages::Message>>,
+    ) -> Option<page::Content> {
+        Some(vec![
+            sections.insert(mode_and_colors()),
+            sections.insert(live_preview()),
+            sections.insert(style()),
+            sections.insert(font()),
+            sections.insert(window_management()),
+            sections.insert(experimental()),
+            sections.insert(reset_button()),
+        ])
+    }
+
+    fn header_view(&self) -> Option<Element<'_, crate::pages::Message>> {
+        if self.file_hovering {
+            let target = text::heading(fl!("import-theme", "drop"))
+                .apply(container)
+                .width(Length::Fill)
+                .padding(self.theme_builder.spacing.space_m)
+                .align_x(alignment::Horizontal::Center)
+                .style(cosmic::theme::Container::Primary)
+                .apply(Element::from);
+
+            return Some(target);
+        }
+
+        let active_profile = self.config.active_look_profile();
+
+        let content = row::with_capacity(14)
+            .spacing(self.theme_builder.spacing.space_xxs)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .push_maybe(
+                self.read_only
+                    .then(|| text::body(fl!("theme-read-only", "short"))),
+            )
+            .push_maybe((!self.look_profiles.is_empty()).then(|| {
+                let names = self.look_profiles.clone();
+                dropdown(
+                    &self.look_profiles,
+                    active_profile
+                        .and_then(|active| self.look_profiles.iter().position(|n| n == active)),
+                    move |index| Message::LoadLookProfile(names[index].clone()),
+                )
+            }))
+            .push_maybe(active_profile.map(|name| {
+                button::standard(fl!("look-profiles", "save-button"))
+                    .on_press(Message::SaveLookProfile(name.to_owned()))
+            }))
+            .push(
+                button::icon(icon::from_name("edit-undo-symbolic")).on_press_maybe(
+                    (!self.read_only && !self.undo_history.is_empty()).then_some(Message::Undo),
+                ),
+            )
+            .push(
+                button::icon(icon::from_name("edit-redo-symbolic")).on_press_maybe(
+                    (!self.read_only && !self.redo_history.is_empty()).then_some(Message::Redo),
+                ),
+            )
+            .push_maybe(self.preview.is_some().then(|| {
+                button::standard(fl!("preview-mode", "discard"))
+                    .on_press(Message::PreviewMode(false))
+            }))
+            .push_maybe(self.preview.is_some().then(|| {
+                button::suggested(fl!("preview-mode", "apply"))
+                    .on_press_maybe((!self.read_only).then_some(Message::CommitPreview))
+            }))
+            .push(
+                button::standard(fl!("import"))
+                    .on_press_maybe((!self.read_only).then_some(Message::StartImport)),
+            )
+            .push_maybe(
+                (!self.recent_theme_files.is_empty())
+                    .then(|| dropdown(&self.recent_theme_files, None, Message::ImportRecent)),
+            )
+            .push(
+                button::standard(fl!("import-theme", "clipboard"))
+                    .on_press_maybe((!self.read_only).then_some(Message::ImportFromClipboard)),
+            )
+            .push(button::standard(fl!("export")).on_press(Message::StartExport))
+            .push(
+                button::standard(fl!("export-theme", "clipboard"))
+                    .on_press(Message::ExportToClipboard),
+            )
+            .push(button::standard(fl!("export-both")).on_press(Message::StartExportBundle))
+            .push(button::standard(fl!("theme-qr", "show")).on_press(Message::ShowThemeQr))
+            .apply(container)
+            .width(Length::Fill)
+            .align_x(alignment::Horizontal::Right)
+            .apply(Element::from)
+            .map(crate::pages::Message::Appearance);
+
+        Some(content)
+    }
+
+    fn info(&self) -> page::Info {
+        page::Info::new("appearance", "preferences-appearance-symbolic")
+            .title(fl!("appearance"))
+            .description(fl!("appearance", "desc"))
+    }
+
+    fn on_enter(
+        &mut self,
+        _: page::Entity,
+        sender: tokio::sync::mpsc::Sender<crate::pages::Message>,
+    ) -> Command<crate::pages::Message> {
+        self.icon_scan_sender = Some(sender.clone());
+        let excludes = self.config.icon_scan_excludes().to_vec();
+        let memo = self.icon_preview_memo.clone();
+        command::future(async move {
+            Message::Entered(fetch_icon_themes(excludes, Some(sender), memo).await)
+        })
+        .map(crate::pages::Message::Appearance)
+    }
+
+    fn on_leave(&mut self) -> Command<crate::pages::Message> {
+        command::message(crate::pages::Message::Appearance(Message::Left))
+    }
+
+    fn context_drawer(&self) -> Option<Element<'_, crate::pages::Message>> {
+        let view = match self.context_view? {
+            ContextView::AutoSwitchSchedule => self.auto_switch_context_view(),
+
+            ContextView::AccentWindowHint => cosmic::widget::column::with_capacity(2)
+                .push(self.color_picker_context_view(
+                    None,
+                    RESET_TO_DEFAULT.as_str().into(),
+                    Message::AccentWindowHint,
+                    |this| &this.accent_window_hint,
+                ))
+                .push(
+                    self.opacity_section(
+                        fl!("window-hint-accent", "opacity"),
+                        self.accent_window_hint.get_applied_color().map(Srgb::from),
+                        self.window_hint_opacity,
+                        Message::WindowHintOpacity,
+                    )
+                    .map(crate::pages::Message::Appearance),
+                )
+                .into(),
+
+            ContextView::ApplicationBackground => cosmic::widget::column::with_capacity(3)
+                .push(
+                    self.contrast_context_view(Message::ApplicationBackground, |this| {
+                        &this.application_background
+                    }),
+                )
+                .push(
+                    self.opacity_section(
+                        fl!("app-background", "opacity"),
+                        Some(self.application_color()),
+                        self.application_opacity,
+                        Message::ApplicationOpacity,
+                    )
+                    .map(crate::pages::Message::Appearance),
+                )
+                .push(
+                    self.gradient_section()
+                        .map(crate::pages::Message::Appearance),
+                )
+                .into(),
+
+            ContextView::ContainerBackground => cosmic::widget::column::with_capacity(2)
+                .push(self.color_picker_context_view(
+                    Some(fl!("container-background", "desc-detail").into()),
+                    fl!("container-background", "reset").into(),
+                    Message::ContainerBackground,
+                    |this| &this.container_background,
+                ))
+                .push(
+                    self.opacity_section(
+                        fl!("container-background", "opacity"),
+                        Some(self.container_color()),
+                        self.container_opacity,
+                        Message::ContainerOpacity,
+                    )
+                    .map(crate::pages::Message::Appearance),
+                )
+                .into(),
+
+            ContextView::ControlComponent => self.color_picker_context_view(
+                None,
+                RESET_TO_DEFAULT.as_str().into(),
+                Message::ControlComponent,
+                |this| &this.control_component,
+            ),
+
+            ContextView::CustomAccent => self.custom_accent_context_view(),
+
+            ContextView::CustomRoundness => self.custom_roundness_context_view(),
+
+            ContextView::ConfirmOverwrite => self.confirm_overwrite_context_view(),
+
+            ContextView::ConfirmRestoreDefaults => self.confirm_restore_defaults_context_view(),
+
+            ContextView::Experimental => self.experimental_context_view(),
+
+            ContextView::ImportPreview => self.import_context_view(),
+
+            ContextView::InterfaceText => self.text_tint_context_view(),
+
+            ContextView::SampleApp(kind) => self.sample_app_context_view(kind),
+
+            ContextView::SeedColor => self.seed_context_view(),
+
+            ContextView::SelectionColor => self.selection_context_view(),
+
+            ContextView::DayAccent => self.color_picker_context_view(
+                None,
+                RESET_TO_DEFAULT.as_str().into(),
+                Message::DayAccent,
+                |this| &this.day_accent,
+            ),
+
+            ContextView::NightAccent => self.color_picker_context_view(
+                None,
+                RESET_TO_DEFAULT.as_str().into(),
+                Message::NightAccent,
+                |this| &this.night_accent,
+            ),
+
+            ContextView::ThemeQr => self.theme_qr_context_view(),
+        };
+
+        Some(view)
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn mode_and_colors() -> Section<crate::pages::Message> {
+    Section::default()
+        .title(fl!("mode-and-colors"))
+        .descriptions(vec![
+            // 0
+            fl!("auto-switch").into(),
+            //1
+            fl!("accent-color").into(),
+            //2
+            fl!("app-background").into(),
+            //3
+            fl!("container-background").into(),
+            fl!("container-background", "desc").into(),
+            fl!("container-background", "desc-detail").into(),
+            fl!("container-background", "reset").into(),
+            // 7
+            fl!("text-tint").into(),
+            fl!("text-tint", "desc").into(),
+            // 9
+            fl!("control-tint").into(),
+            fl!("control-tint", "desc").into(),
+            // 11
+            fl!("window-hint-accent-toggle").into(),
+            fl!("window-hint-accent").into(),
+            // 13
+            fl!("dark").into(),
+            fl!("light").into(),
+            // 15
+            fl!("decoration-accent-only").into(),
+            fl!("decoration-accent-only", "desc").into(),
+            // 17
+            fl!("selection-color").into(),
+            fl!("selection-color", "desc").into(),
+            // 19
+            fl!("window-hint-accent", "link").into(),
+            // 20
+            fl!("mode-accents").into(),
+            fl!("mode-accents", "desc").into(),
+            fl!("mode-accents", "day").into(),
+            fl!("mode-accents", "night").into(),
+            // 24
+            fl!("high-contrast").into(),
+            fl!("high-contrast", "desc").into(),
+        ])
+        .view::<Page>(|_binder, page, section| {
+            let descriptions = &section.descriptions;
+            let palette = &page.theme_builder.palette.as_ref();
+            let cur_accent = page
+                .theme_builder
+                .accent
+                .map_or(palette.accent_blue, Srgba::from);
+            settings::view_section(&section.title)
+                .add(
+                    container(
+                        cosmic::iced::widget::row![
+                            cosmic::iced::widget::column![
+                                button(
+                                    icon(from_name("illustration-appearance-mode-dark").into(),)
+                                        .width(Length::Fill)
+                                        .height(Length::Fixed(100.0))
+                                )
+                                .style(button::Style::Image)
+                                .padding([8, 0])
+                                .selected(page.theme_mode.is_dark)
+                                .name(fl!("mode-preview", "dark"))
+                                .on_press(Message::DarkMode(true)),
+                                text(&*descriptions[13])
+                            ]
+                            .spacing(8)
+                            .width(Length::FillPortion(1))
+                            .align_items(cosmic::iced_core::Alignment::Center),
+                            cosmic::iced::widget::column![
+                                button(
+                                    icon(from_name("illustration-appearance-mode-light").into(),)
+                                        .width(Length::Fill)
+                                        .height(Length::Fixed(100.0))
+                                )
+                                .style(button::Style::Image)
+                                .selected(!page.theme_mode.is_dark)
+                                .padding([8, 0])
+                                .name(fl!("mode-preview", "light"))
+                                .on_press(Message::DarkMode(false)),
+                                text(&*descriptions[14])
+                            ]
+                            .spacing(8)
+                            .width(Length::FillPortion(1))
+                            .align_items(cosmic::iced_core::Alignment::Center)
+                        ]
+                        .spacing(48)
+                        .align_items(cosmic::iced_core::Alignment::Center)
+                        .width(Length::Fixed(424.0)),
+                    )
+                    .width(Length::Fill)
+                    .align_x(cosmic::iced_core::alignment::Horizontal::Center),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[0])
+                        .description(match page.effective_schedule() {
+                            Some(schedule) if page.day_time => Cow::from(fl!(
+                                "auto-switch",
+                                "custom-dark",
+                                time = schedule.dark.to_string()
+                            )),
+                            Some(schedule) => Cow::from(fl!(
+                                "auto-switch",
+                                "custom-light",
+                                time = schedule.light.to_string()
+                            )),
+                            None if !page.day_time && page.theme_mode.is_dark => {
+                                page.auto_switch_descs[0].clone()
+                            }
+                            None if page.day_time && !page.theme_mode.is_dark => {
+                                page.auto_switch_descs[1].clone()
+                            }
+                            None if page.day_time && page.theme_mode.is_dark => {
+                                page.auto_switch_descs[2].clone()
+                            }
+                            None => page.auto_switch_descs[3].clone(),
+                        })
+                        .toggler(page.theme_mode.auto_switch, Message::Autoswitch),
+                )
+                .apply(|section| {
+                    if page.theme_mode.auto_switch {
+                        section.add(
+                            settings::item::builder(fl!("auto-switch", "schedule"))
+                                .description(if page.auto_switch_schedule.is_some() {
+                                    fl!("auto-switch", "custom")
+                                } else {
+                                    fl!("auto-switch", "sunrise-sunset")
+                                })
+                                .control(
+                                    button::icon(icon::from_name("go-next-symbolic"))
+                                        .on_press(Message::AutoSwitchSchedule),
+                                ),
+                        )
+                    } else {
+                        section
+                    }
+                })
+                .add(
+                    settings::item::builder(&*descriptions[24])
+                        .description(&*descriptions[25])
+                        .toggler(is_high_contrast(&page.theme_builder), Message::HighContrast),
+                )
+                .add(
+                    settings::item::builder(if page.theme_mode.is_dark {
+                        fl!("copy-colors", "from-light")
+                    } else {
+                        fl!("copy-colors", "from-dark")
+                    })
+                    .description(fl!("copy-colors", "desc"))
+                    .control(
+                        button::standard(fl!("copy-colors")).on_press_maybe(
+                            (!page.read_only).then_some(Message::CopyColorsFromOtherMode),
+                        ),
+                    ),
+                )
+                .add(
+                    cosmic::iced::widget::column![
+                        row::with_capacity(5)
+                            .push(text(&*descriptions[1]))
+                            .push(horizontal_space(Length::Fill))
+                            .push(
+                                button::icon(from_name("preferences-desktop-wallpaper-symbolic"))
+                                    .extra_small()
+                                    .on_press(Message::AccentFromWallpaper)
+                            )
+                            .push(
+                                button::icon(from_name("media-playlist-shuffle-symbolic"))
+                                    .extra_small()
+                                    .on_press_maybe(
+                                        (!page.read_only).then_some(Message::RandomizeAccent)
+                                    )
+                            )
+                            .push(color_lock_button(
+                                ColorRole::Accent,
+                                page.locked_colors.contains(&ColorRole::Accent)
+                            ))
+                            .align_items(cosmic::iced_core::Alignment::Center),
+                        scrollable(
+                            row::with_capacity(10)
+                                .extend(page.accent_swatches().into_iter().enumerate().map(
+                                    |(n, swatch)| {
+                                        let button = accent_button(
+                                            n,
+                                            swatch.name,
+                                            swatch.color,
+                                            cur_accent == swatch.color,
+                                            page.color_vision,
+                                        );
+                                        match swatch.saved {
+                                            Some(i) => cosmic::iced::widget::mouse_area(button)
+                                                .on_right_press(Message::PaletteAccentRemove(i))
+                                                .into(),
+                                            None => button,
+                                        }
+                                    }
+                                ))
+                                .push(if let Some(c) = page.custom_accent.get_applied_color() {
+                                    container(
+                                        swatch_button(
+                                            Some(Message::CustomAccent(
+                                                ColorPickerUpdate::ToggleColorPicker,
+                                            )),
+                                            vision::simulate(c, page.color_vision),
+                                            cosmic::iced::Color::from(cur_accent) == c,
+                                            48,
+                                            48,
+                                        )
+                                        .name(swatch_name(
+                                            fl!("accent-name", "custom"),
+                                            Srgb::from(c),
+                                        )),
+                                    )
+                                } else {
+                                    container(
+                                        page.custom_accent
+                                            .picker_button(Message::CustomAccent, None)
+                                            .width(Length::Fixed(48.0))
+                                            .height(Length::Fixed(48.0)),
+                                    )
+                                })
+                                .padding([0, 0, 16, 0])
+                                .spacing(16)
+                        )
+                        .direction(scrollable::Direction::Horizontal(
+                            scrollable::Properties::new()
+                        ))
+                        .id(page.accent_scrollable.clone())
+                    ]
+                    .padding([16, 24, 0, 24])
+                    .spacing(8),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[20])
+                        .description(&*descriptions[21])
+                        .toggler(
+                            page.config.separate_mode_accents(),
+                            Message::SeparateModeAccents,
+                        ),
+                )
+                .apply(|section| {
+                    if page.config.separate_mode_accents() {
+                        section
+                            .add(settings::item::builder(&*descriptions[22]).control(
+                                color_picker_control(&page.day_accent, Message::DayAccent),
+                            ))
+                            .add(settings::item::builder(&*descriptions[23]).control(
+                                color_picker_control(&page.night_accent, Message::NightAccent),
+                            ))
+                    } else {
+                        section
+                    }
+                })
+                .add(
+                    settings::item::builder(&*descriptions[2]).control(
+                        match page.background_gradient() {
+                            Some(gradient) => row::with_capacity(2)
+                                .push(color_lock_button(
+                                    ColorRole::ApplicationBackground,
+                                    page.locked_colors
+                                        .contains(&ColorRole::ApplicationBackground),
+                                ))
+                                .push(gradient_button(
+                                    gradient,
+                                    Message::ApplicationBackground(
+                                        ColorPickerUpdate::ToggleColorPicker,
+                                    ),
+                                ))
+                                .spacing(page.theme_builder.spacing.space_xxs)
+                                .align_items(cosmic::iced_core::Alignment::Center)
+                                .into(),
+                            None => page.lockable_color_control(
+                                ColorRole::ApplicationBackground,
+                                &page.application_background,
+                                Message::ApplicationBackground,
+                            ),
+                        },
+                    ),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[3])
+                        .description(&*descriptions[4])
+                        .control(page.lockable_color_control(
+                            ColorRole::ContainerBackground,
+                            &page.container_background,
+                            Message::ContainerBackground,
+                        )),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[7])
+                        .description(&*descriptions[8])
+                        .control(page.lockable_color_control(
+                            ColorRole::InterfaceText,
+                            &page.interface_text,
+                            Message::InterfaceText,
+                        )),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[9])
+                        .description(&*descriptions[10])
+                        .control(page.lockable_color_control(
+                            ColorRole::ControlComponent,
+                            &page.control_component,
+                            Message::ControlComponent,
+                        )),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[17])
+                        .description(&*descriptions[18])
+                        .control(color_picker_control(
+                            &page.selection_color,
+                            Message::SelectionColor,
+                        )),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[11])
+                        .toggler(page.no_custom_window_hint, Message::UseDefaultWindowHint),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[15])
+                        .description(&*descriptions[16])
+                        .toggler(
+                            page.config.decoration_accent_only(),
+                            Message::DecorationAccentOnly,
+                        ),
+                );
+            if !page.no_custom_window_hint {
+                let link = cosmic::iced::widget::tooltip(
+                    button::icon(from_name("insert-link-symbolic"))
+                        .on_press(Message::UseDefaultWindowHint(true)),
+                    text(&*descriptions[19]),
+                    cosmic::iced::widget::tooltip::Position::Bottom,
+                );
+
+                section = section.add(
+                    settings::item::builder(&*descriptions[12]).control(
+                        row::with_capacity(2)
+                            .push(link)
+                            .push(page.lockable_color_control(
+                                ColorRole::WindowHint,
+                                &page.accent_window_hint,
+                                Message::AccentWindowHint,
+                            ))
+                            .spacing(page.theme_builder.spacing.space_xxs)
+                            .align_items(cosmic::iced_core::Alignment::Center),
+                    ),
+                );
+            }
+            section
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+pub fn live_preview() -> Section<crate::pages::Message> {
+    Section::default()
+        .title(fl!("live-preview"))
+        .descriptions(vec![fl!("live-preview", "desc").into()])
+        .view::<Page>(|_binder, page, section| {
+            settings::view_section(&section.title)
+                .add(settings::item_row(vec![text::caption(
+                    &*section.descriptions[0],
+                )
+                .into()]))
+                .add(page.live_preview.view())
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn style() -> Section<crate::pages::Message> {
+    Section::default()
+        .title(fl!("style"))
+        .descriptions(vec![
+            fl!("style", "round").into(),
+            fl!("style", "slightly-round").into(),
+            fl!("style", "square").into(),
+            fl!("style", "square-floating-panel").into(),
+            fl!("style", "custom").into(),
+            fl!("style", "custom-desc").into(),
+            fl!("style", "edit").into(),
+        ])
+        .view::<Page>(|_binder, page, section| {
+            let descriptions = &section.descriptions;
+
+            settings::view_section(&section.title)
+                .add(
+                    container(
+                        cosmic::iced::widget::row![
+                            cosmic::iced::widget::column![
+                                button(
+                                    icon(
+                                        from_name(if page.theme_mode.is_dark {
+                                            "illustration-appearance-dark-style-round"
+                                        } else {
+                                            "illustration-appearance-light-style-round"
+                                        })
+                                        .into()
+                                    )
+                                    .width(Length::Fill)
+                                    .height(Length::Fixed(100.0))
+                                )
+                                .selected(matches!(page.roundness, Roundness::Round))
+                                .style(button::Style::Image)
+                                .padding(8)
+                                .name(descriptions[0].to_string())
+                                .on_press(Message::Roundness(Roundness::Round)),
+                                text(&*descriptions[0])
+                            ]
+                            .spacing(8)
+                            .width(Length::FillPortion(1))
+                            .align_items(cosmic::iced_core::Alignment::Center),
+                            cosmic::iced::widget::column![
+                                button(
+                                    icon(
+                                        from_name(if page.theme_mode.is_dark {
+                                            "illustration-appearance-dark-style-slightly-round"
+                                        } else {
+                                            "illustration-appearance-light-style-slightly-round"
+                                        })
+                                        .into()
+                                    )
+                                    .width(Length::Fill)
+                                    .height(Length::Fixed(100.0))
+                                )
+                                .selected(matches!(page.roundness, Roundness::SlightlyRound))
+                                .style(button::Style::Image)
+                                .padding(8)
+                                .name(descriptions[1].to_string())
+                                .on_press(Message::Roundness(Roundness::SlightlyRound)),
+                                text(&*descriptions[1])
+                            ]
+                            .spacing(8)
+                            .width(Length::FillPortion(1))
+                            .align_items(cosmic::iced_core::Alignment::Center),
+                            cosmic::iced::widget::column![
+                                button(
+                                    icon(
+                                        from_name(if page.theme_mode.is_dark {
+                                            "illustration-appearance-dark-style-square"
+                                        } else {
+                                            "illustration-appearance-light-style-square"
+                                        })
+                                        .into(),
+                                    )
+                                    .width(Length::Fill)
+                                    .height(Length::Fixed(100.0))
+                                )
+                                .width(Length::FillPortion(1))
+                                .selected(matches!(page.roundness, Roundness::Square))
+                                .style(button::Style::Image)
+                                .padding(8)
+                                .name(descriptions[2].to_string())
+                                .on_press(Message::Roundness(Roundness::Square)),
+                                text(&*descriptions[2])
+                            ]
+                            .spacing(8)
+                            .align_items(cosmic::iced_core::Alignment::Center)
+                            .width(Length::FillPortion(1))
+                        ]
+                        .spacing(12)
+                        .width(Length::Fixed(628.0))
+                        .align_items(cosmic::iced_core::Alignment::Center),
+                    )
+                    .width(Length::Fill)
+                    .align_x(cosmic::iced_core::alignment::Horizontal::Center),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[4])
+                        .description(&*descriptions[5])
+                        .control(
+                            button::standard(&*descriptions[6])
+                                .selected(matches!(page.roundness, Roundness::Custom(_)))
+                                .on_press(Message::CustomRoundness),
+                        ),
+                )
+                // Floating panels take the roundness of the theme, which may be unexpected.
+                .apply(|view| {
+                    if matches!(page.roundness, Roundness::Square) && page.floating_panel {
+                        view.add(text::caption(&*descriptions[3]))
+                    } else {
+                        view
+                    }
+                })
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+pub fn font() -> Section<crate::pages::Message> {
+    Section::default()
+        .title(fl!("interface-font"))
+        .descriptions(vec![
+            fl!("interface-font", "family").into(),
+            fl!("interface-font", "sample").into(),
+            fl!("interface-font", "monospace").into(),
+            fl!("interface-font", "scaling").into(),
+        ])
+        .view::<Page>(|_binder, page, section| {
+            let descriptions = &section.descriptions;
+            let interface = page.config.interface_font();
+            let monospace = page.config.monospace_font();
+
+            settings::view_section(&section.title)
+                .add(
+                    settings::item::builder(&*descriptions[0]).control(name_dropdown(
+                        &page.font_families,
+                        interface,
+                        Message::InterfaceFont,
+                    )),
+                )
+                .add(settings::item_row(vec![font_sample(
+                    &descriptions[1],
+                    interface,
+                )]))
+                .add(
+                    settings::item::builder(&*descriptions[2]).control(name_dropdown(
+                        &page.monospace_families,
+                        monospace,
+                        Message::MonospaceFont,
+                    )),
+                )
+                .add(settings::item_row(vec![font_sample(
+                    MONOSPACE_SAMPLE,
+                    monospace,
+                )]))
+                .add(
+                    settings::item::builder(&*descriptions[3]).flex_control(
+                        row::with_capacity(2)
+                            .push(
+                                cosmic::widget::slider(
+                                    0.5..=2.0,
+                                    page.text_scaling,
+                                    Message::TextScaling,
+                                )
+                                .step(0.05)
+                                .width(Length::Fill),
+                            )
+                            .push(text(fl!(
+                                "interface-font",
+                                "scaling-size",
+                                factor = format!("{:.2}", page.text_scaling),
+                                points = format!("{:.1}", page.text_scaling * BODY_TEXT_POINTS)
+                            )))
+                            .spacing(page.theme_builder.spacing.space_s)
+                            .align_items(cosmic::iced_core::Alignment::Center),
+                    ),
+                )
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn window_management() -> Section<crate::pages::Message> {
+    Section::default()
+        .title(fl!("window-management"))
+        .descriptions(vec![
+            fl!("window-management", "active-hint").into(),
+            fl!("window-management", "gaps").into(),
+            fl!("window-management", "outer-gaps").into(),
+            fl!("window-management", "outer-gaps-desc").into(),
+        ])
+        .view::<Page>(|_binder, page, section| {
+            let descriptions = &section.descriptions;
+
+            let sync_description = match (page.config.sync_layout(), page.theme_mode.is_dark) {
+                (true, _) => fl!("sync-layout", "desc"),
+                (false, true) => fl!("sync-layout", "dark-only"),
+                (false, false) => fl!("sync-layout", "light-only"),
+            };
+
+            settings::view_section(&section.title)
+                .add(
+                    settings::item::builder(fl!("sync-layout"))
+                        .description(sync_description)
+                        .toggler(page.config.sync_layout(), Message::SyncShape),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[0]).control(bounded_spin_button(
+                        SizeField::ActiveHint,
+                        page.theme_builder.active_hint,
+                        page.size_drafts.get(&SizeField::ActiveHint),
+                        Message::WindowHintSize,
+                    )),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[1]).control(bounded_spin_button(
+                        SizeField::Gap,
+                        page.theme_builder.gaps.1,
+                        page.size_drafts.get(&SizeField::Gap),
+                        Message::GapSize,
+                    )),
+                )
+                .add(
+                    settings::item::builder(&*descriptions[2])
+                        .description(&*descriptions[3])
+                        .control(bounded_spin_button(
+                            SizeField::OuterGap,
+                            page.theme_builder.gaps.0,
+                            page.size_drafts.get(&SizeField::OuterGap),
+                            Message::OuterGapSize,
+                        )),
+                )
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+pub fn experimental() -> Section<crate::pages::Message> {
+    Section::default()
+        .descriptions(vec![fl!("experimental-settings").into()])
+        .view::<Page>(|_binder, _page, section| {
+            let descriptions = &*section.descriptions;
+
+            let control = row::with_children(vec![
+                horizontal_space(Length::Fill).into(),
+                icon::from_name("go-next-symbolic").size(16).into(),
+            ]);
+
+            settings::view_section("")
+                .add(
+                    settings::item::builder(&*descriptions[0])
+                        .control(control)
+                        .apply(container)
+                        .style(cosmic::theme::Container::List)
+                        .apply(button)
+                        .style(cosmic::theme::Button::Transparent)
+                        .on_press(Message::ExperimentalContextDrawer),
+                )
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn reset_button() -> Section<crate::pages::Message> {
+    Section::default()
+        .descriptions(vec![
+            fl!("reset-to-default").into(),
+            fl!("randomize-colors").into(),
+            fl!("seed-color").into(),
+            fl!("sample-app", "open").into(),
+            fl!("restore-defaults").into(),
+        ])
+        .view::<Page>(|_binder, page, section| {
+            let descriptions = &section.descriptions;
+            row::with_capacity(6)
+                .push(
+                    button::standard(&*descriptions[1])
+                        .on_press_maybe((!page.read_only).then_some(Message::RandomizeColors)),
+                )
+                .push(
+                    button::standard(&*descriptions[2]).on_press_maybe(
+                        (!page.read_only)
+                            .then_some(Message::SeedColor(ColorPickerUpdate::ToggleColorPicker)),
+                    ),
+                )
+                .push(
+                    button::standard(&*descriptions[3])
+                        .on_press(Message::PreviewSampleApp(SampleKind::default())),
+                )
+                .push_maybe((!page.resettable.is_empty()).then(|| {
+                    dropdown(
+                        &page.reset_scopes,
+                        ResetScope::ALL
+                            .iter()
+                            .position(|scope| *scope == page.reset_scope),
+                        |i| Message::SelectResetScope(ResetScope::ALL[i]),
+                    )
+                }))
+                .push_maybe((!page.resettable.is_empty()).then(|| {
+                    button::standard(&*descriptions[0]).on_press_maybe(
+                        (!page.read_only && page.resettable.contains(&page.reset_scope))
+                            .then_some(Message::ResetScope(page.reset_scope)),
+                    )
+                }))
+                .push(
+                    button::standard(&*descriptions[4]).on_press_maybe(
+                        (!page.read_only).then_some(Message::StartRestoreAllDefaults),
+                    ),
+                )
+                .spacing(page.theme_builder.spacing.space_xxs)
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+impl page::AutoBind<crate::pages::Message> for Page {}
+
+/// Maps keyboard shortcuts to page actions while the appearance page is active.
+///
+/// Without modifiers, the arrow keys move focus through the accent row while a swatch has
+/// focus, where Enter or Space applies it. Super+Shift+T switches between dark and light mode.
+pub fn shortcut(key: &Key, modifiers: Modifiers) -> Option<Message> {
+    if modifiers.logo() && modifiers.shift() && !modifiers.control() && !modifiers.alt() {
+        return matches!(key.as_ref(), Key::Character("t" | "T"))
+            .then_some(Message::ToggleDarkMode);
+    }
+
+    if modifiers.is_empty() {
+        return match key.as_ref() {
+            Key::Named(Named::ArrowLeft) => Some(Message::MoveAccentFocus(-1)),
+            Key::Named(Named::ArrowRight) => Some(Message::MoveAccentFocus(1)),
+            _ => None,
+        };
+    }
+
+    if !modifiers.control() {
+        return None;
+    }
+
+    match key.as_ref() {
+        Key::Character("o") => Some(Message::StartImport),
+        Key::Character("s") => Some(Message::StartExport),
+        Key::Character("z" | "Z") if modifiers.shift() => Some(Message::Redo),
+        Key::Character("z") => Some(Message::Undo),
+        _ => None,
+    }
+}
+
+/// Toggles whether a color is kept when randomizing colors.
+fn color_lock_button(role: ColorRole, locked: bool) -> Element<'static, Message> {
+    button::icon(from_name(if locked {
+        "changes-prevent-symbolic"
+    } else {
+        "changes-allow-symbolic"
+    }))
+    .extra_small()
+    .on_press(Message::ToggleColorLock(role))
+    .into()
+}
+
+/// Sets and applies the color of a color picker.
+fn set_picker_color(model: &mut ColorPickerModel, color: Srgb) {
+    _ = model.update::<app::Message>(ColorPickerUpdate::ActiveColor(Hsv::from_color(color)));
+    _ = model.update::<app::Message>(ColorPickerUpdate::AppliedColor);
+}
+
+/// A picker button for a theme color, or an "Auto" button if the color is derived from the theme.
+fn color_picker_control(
+    model: &ColorPickerModel,
+    on_update: fn(ColorPickerUpdate) -> Message,
+) -> Element<'_, Message> {
+    if model.get_applied_color().is_some() {
+        model
+            .picker_button(on_update, Some(24))
+            .width(Length::Fixed(48.0))
+            .height(Length::Fixed(24.0))
+            .into()
+    } else {
+        button::text(fl!("auto"))
+            .trailing_icon(from_name("go-next-symbolic"))
+            .on_press(on_update(ColorPickerUpdate::ToggleColorPicker))
+            .into()
+    }
+}
+
+/// A palette accent button, named by its tooltip, and shown as it appears with the given
+/// color vision.
+fn accent_button(
+    n: usize,
+    name: String,
+    color: Srgba,
+    selected: bool,
+    vision: ColorVisionMode,
+) -> Element<'static, Message> {
+    let label = swatch_name(name.clone(), color.color);
+
+    cosmic::iced::widget::tooltip(
+        swatch_button(
+            Some(Message::PaletteAccent(color.into())),
+            vision::simulate(color.into(), vision),
+            selected,
+            48,
+            48,
+        )
+        .id(accent_swatch_id(n))
+        .name(label),
+        text(name),
+        cosmic::iced::widget::tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// ID of the nth accent swatch, by which it is focused.
+fn accent_swatch_id(n: usize) -> cosmic::widget::Id {
+    cosmic::widget::Id::new(format!("accent-swatch-{n}"))
+}
+
+/// The accent swatch to focus when moving by `step` from the focused widget, if a
+/// swatch has focus.
+fn next_accent_swatch(focused: &cosmic::widget::Id, count: usize, step: isize) -> Option<usize> {
+    let n = (0..count).position(|n| accent_swatch_id(n) == *focused)?;
+    Some(n.saturating_add_signed(step).min(count - 1))
+}
+
+/// The settings which differ between the current theme and an imported theme,
+/// with their current and imported values side by side.
+fn import_changes<'a>(current: &ThemeBuilder, imported: &ThemeBuilder) -> Element<'a, Message> {
+    let colors = [
+        (
+            fl!("accent-color"),
+            current.accent.map(Color::from),
+            imported.accent.map(Color::from),
+        ),
+        (
+            fl!("app-background"),
+            current.bg_color.map(Color::from),
+            imported.bg_color.map(Color::from),
+        ),
+        (
+            fl!("container-background"),
+            current.primary_container_bg.map(Color::from),
+            imported.primary_container_bg.map(Color::from),
+        ),
+        (
+            fl!("text-tint"),
+            current.text_tint.map(Color::from),
+            imported.text_tint.map(Color::from),
+        ),
+        (
+            fl!("control-tint"),
+            current.neutral_tint.map(Color::from),
+            imported.neutral_tint.map(Color::from),
+        ),
+        (
+            fl!("window-hint-accent"),
+            current.window_hint.map(Color::from),
+            imported.window_hint.map(Color::from),
+        ),
+    ];
+
+    let sizes = [
+        (
+            fl!("window-management", "gaps"),
+            current.gaps.1.to_string(),
+            imported.gaps.1.to_string(),
+        ),
+        (
+            fl!("window-management", "active-hint"),
+            current.active_hint.to_string(),
+            imported.active_hint.to_string(),
+        ),
+        (
+            fl!("import-preview", "roundness"),
+            format!("{:.0}", current.corner_radii.radius_m[0]),
+            format!("{:.0}", imported.corner_radii.radius_m[0]),
+        ),
+    ];
+
+    let change = |old: Element<'a, Message>, new: Element<'a, Message>| {
+        row::with_capacity(3)
+            .push(old)
+            .push(icon::from_name("go-next-symbolic").size(16).icon())
+            .push(new)
+            .spacing(8)
+            .align_items(cosmic::iced_core::Alignment::Center)
+    };
+
+    let swatch = |color: Option<Color>| -> Element<'a, Message> {
+        match color {
+            Some(color) => color_button(None, color, false, 24, 24),
+            None => text::caption(fl!("auto")).into(),
+        }
+    };
+
+    let mut section = settings::view_section(fl!("import-preview"));
+    let mut changed = false;
+
+    for (name, old, new) in colors {
+        if old != new {
+            changed = true;
+            section = section
+                .add(settings::item::builder(name).control(change(swatch(old), swatch(new))));
+        }
+    }
+
+    for (name, old, new) in sizes {
+        if old != new {
+            changed = true;
+            section = section.add(
+                settings::item::builder(name)
+                    .control(change(text::body(old).into(), text::body(new).into())),
+            );
+        }
+    }
+
+    if changed {
+        section.into()
+    } else {
+        text::caption(fl!("import-preview", "unchanged")).into()
+    }
+}
+
+/// A button for selecting a color or gradient.
+pub fn color_button<'a, Message: 'a + Clone>(
+    on_press: Option<Message>,
+    color: cosmic::iced::Color,
+    selected: bool,
+    width: u16,
+    height: u16,
+) -> Element<'a, Message> {
+    swatch_button(on_press, color, selected, width, height).into()
+}
+
+/// A color button which can still be given an accessible name.
+fn swatch_button<'a, Message: 'a + Clone>(
+    on_press: Option<Message>,
+    color: cosmic::iced::Color,
+    selected: bool,
+    width: u16,
+    height: u16,
+) -> cosmic::widget::Button<'a, Message> {
+    let content = if color.a < 1.0 {
+        checkered_swatch(color, width, height)
+    } else {
+        color_image(
+            wallpaper::Color::Single([color.r, color.g, color.b]),
+            width,
+            height,
+            None,
+        )
+    };
+
+    button(content)
+        .padding(0)
+        .selected(selected)
+        .style(button::Style::Image)
+        .on_press_maybe(on_press)
+        .width(Length::Fixed(f32::from(width)))
+        .height(Length::Fixed(f32::from(height)))
+}
+
+/// The name announced by assistive technology for an accent swatch, which includes
+/// its hex value.
+fn swatch_name(name: String, color: Srgb) -> String {
+    fl!(
+        "accent-swatch",
+        name = name,
+        hex = ColorFormat::Hex.format(color)
+    )
+}
+
+/// A translucent color drawn over a checkerboard, so that its transparency is visible.
+fn checkered_swatch<'a, Message: 'a>(
+    color: Color,
+    width: u16,
+    height: u16,
+) -> Element<'a, Message> {
+    let square = |checker: Color| {
+        container(horizontal_space(Length::Fixed(f32::from(CHECKER_SIZE))))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(cosmic::theme::Container::custom(move |_| {
+                container::Appearance {
+                    background: Some(Background::Color(color)),
+                    ..Default::default()
+                }
+            }))
+            .apply(container)
+            .width(Length::Fixed(f32::from(CHECKER_SIZE)))
+            .height(Length::Fixed(f32::from(CHECKER_SIZE)))
+            .style(cosmic::theme::Container::custom(move |_| {
+                container::Appearance {
+                    background: Some(Background::Color(checker)),
+                    ..Default::default()
+                }
+            }))
+    };
+
+    let rows = (0..height / CHECKER_SIZE).map(|y| {
+        row::with_children(
+            (0..width / CHECKER_SIZE)
+                .map(|x| {
+                    square(if (x + y) % 2 == 0 {
+                        Color::WHITE
+                    } else {
+                        Color::from_rgb(0.8, 0.8, 0.8)
+                    })
+                    .into()
+                })
+                .collect(),
+        )
+        .into()
+    });
+
+    cosmic::widget::column::with_children(rows.collect()).into()
+}
+
+/// A swatch of a background gradient, which opens its color picker.
+fn gradient_button(gradient: &BackgroundGradient, on_press: Message) -> Element<'_, Message> {
+    button(color_image(gradient.preview(), 48, 24, None))
+        .padding(0)
+        .style(button::Style::Image)
+        .on_press(on_press)
+        .width(Length::Fixed(48.0))
+        .height(Length::Fixed(24.0))
+        .into()
+}
+
+/// Reads and parses a theme file, which is previewed before it is imported.
+fn import_file(path: PathBuf) -> Command<app::Message> {
+    Command::perform(
+        {
+            let path = path.clone();
+            async move { tokio::fs::read_to_string(path).await }
+        },
+        move |res| {
+            let message = match res
+                .map_err(|_| import::Error::Unreadable)
+                .and_then(|s| ImportedTheme::parse(&s))
+            {
+                Ok(theme) => Message::ImportLoaded(theme, Some(path)),
+                Err(why) => {
+                    tracing::error!(?why, "failed to import a file for a custom theme.");
+                    Message::ImportError(why)
+                }
+            };
+
+            crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+        },
+    )
+}
+
+/// Opens a file chooser for selecting a theme file to import.
+fn select_import_file(on_select: fn(Arc<SelectedFiles>) -> Message) -> Command<app::Message> {
+    Command::perform(
+        async {
+            SelectedFiles::open_file()
+                .modal(true)
+                .filter(FileFilter::glob(FileFilter::new("ron"), "*.ron"))
+                .send()
+                .await?
+                .response()
+        },
+        move |res| {
+            let message = match res {
+                Ok(f) => on_select(Arc::new(f)),
+                Err(why) if is_cancelled(&why) => Message::FileChooserClosed,
+                Err(why) => {
+                    tracing::error!(
+                        ?why,
+                        "failed to select a file for importing a custom theme."
+                    );
+                    Message::ImportError(import::Error::Unreadable)
+                }
+            };
+
+            crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+        },
+    )
+}
+
+/// Opens a file chooser for selecting where to export a file.
+fn select_export_file(
+    name: String,
+    on_select: fn(Arc<SelectedFiles>) -> Message,
+) -> Command<app::Message> {
+    Command::perform(
+        async move {
+            SelectedFiles::save_file()
+                .modal(true)
+                .current_name(Some(name.as_str()))
+                .send()
+                .await?
+                .response()
+        },
+        move |res| {
+            let message = match res {
+                Ok(f) => on_select(Arc::new(f)),
+                Err(why) if is_cancelled(&why) => Message::FileChooserClosed,
+                Err(why) => {
+                    tracing::error!(
+                        ?why,
+                        "failed to select a file for exporting a custom theme."
+                    );
+                    Message::ExportError
+                }
+            };
+
+            crate::Message::PageMessage(crate::pages::Message::Appearance(message))
+        },
+    )
+}
+
+/// Writes an exported file in the background.
+fn write_export_file(path: PathBuf, contents: String, recent: bool) -> Command<app::Message> {
+    let recent = recent.then(|| path.clone());
+
+    Command::perform(
+        async move { tokio::fs::write(path, contents).await },
+        move |res| {
+            if res.is_ok() {
+                crate::Message::PageMessage(crate::pages::Message::Appearance(
+                    Message::ExportSuccess(recent),
+                ))
+            } else {
+                tracing::error!("failed to write an exported file.");
+                crate::Message::PageMessage(crate::pages::Message::Appearance(Message::ExportError))
+            }
+        },
+    )
+}
+
+/// Whether a file chooser was closed by the user, rather than failing.
+fn is_cancelled(why: &ashpd::Error) -> bool {
+    matches!(
+        why,
+        ashpd::Error::Response(ashpd::desktop::ResponseError::Cancelled)
+    )
+}
+
+/// The local path of the file selected in a file chooser.
+fn selected_path(files: &SelectedFiles) -> Option<PathBuf> {
+    let uri = files.uris().first()?;
+
+    if uri.scheme() != "file" {
+        return None;
+    }
+
+    uri.to_file_path().ok()
+}
+
+/// Why a theme could not be written.
+#[derive(Debug)]
+enum ThemeWriteError {
+    /// The built theme is invalid, so nothing was written.
+    Invalid,
+    /// The config could not be opened or written to.
+    Config(cosmic::cosmic_config::Error),
+}
+
+/// Writes a theme builder, and the theme built from it, to the configs of a color mode.
+///
+/// Nothing is written if the built theme is invalid.
+fn write_theme_builder(builder: &ThemeBuilder, is_dark: bool) -> Result<(), ThemeWriteError> {
+    write_theme_config(builder, builder.clone().build(), is_dark)
+}
+
+/// A color with an opacity, or `None` to keep the theme's default when it is opaque.
+///
+/// A translucent default is written with the `fallback` color, since the default has no
+/// opacity of its own.
+fn with_opacity(
+    color: Option<Srgb>,
+    fallback: impl FnOnce() -> Srgb,
+    opacity: f32,
+) -> Option<Srgba> {
+    let color = match color {
+        Some(color) => color,
+        None if opacity < 1.0 => fallback(),
+        None => return None,
+    };
+
+    Some(Srgba::from_color(color).with_alpha(opacity))
+}
+
+/// Steps a size by one pixel, without exceeding `max`.
+fn step_size(size: u32, max: u32, message: spin_button::Message) -> u32 {
+    match message {
+        spin_button::Message::Increment => size.saturating_add(1).min(max),
+        spin_button::Message::Decrement => size.saturating_sub(1),
+    }
+}
+
+/// A spin button for a size from zero to the field's maximum, which cannot be stepped
+/// past either end.
+///
+/// The size can also be typed in, and is applied when the input is submitted.
+fn bounded_spin_button(
+    field: SizeField,
+    size: u32,
+    draft: Option<&String>,
+    on_change: fn(spin_button::Message) -> Message,
+) -> Element<'static, Message> {
+    let max = field.max();
+    let input = draft.map_or_else(|| size.to_string(), Clone::clone);
+
+    row::with_capacity(3)
+        .push(
+            button::icon(from_name("list-remove-symbolic"))
+                .on_press_maybe((size > 0).then(|| on_change(spin_button::Message::Decrement))),
+        )
+        .push(
+            cosmic::widget::text_input("", input)
+                .on_input(move |input| Message::SizeInput(field, input))
+                .on_submit(Message::SizeSubmit(field))
+                .width(Length::Fixed(56.0)),
+        )
+        .push(
+            button::icon(from_name("list-add-symbolic"))
+                .on_press_maybe((size < max).then(|| on_change(spin_button::Message::Increment))),
+        )
+        .align_items(cosmic::iced_core::Alignment::Center)
+        .into()
+}
+
+/// Whether a color is one of the palette's accents, ignoring the opacity of the palette.
+fn is_palette_accent(color: Srgb, theme: &Theme) -> bool {
+    let palette = &theme.palette;
+
+    [
+        palette.accent_blue,
+        palette.accent_green,
+        palette.accent_indigo,
+        palette.accent_orange,
+        palette.accent_pink,
+        palette.accent_purple,
+        palette.accent_red,
+        palette.accent_warm_grey,
+        palette.accent_yellow,
+    ]
+    .iter()
+    .any(|accent| accent.color == color)
+}
+
+/// The default theme builder of a color mode, in normal or high contrast.
+fn baseline_builder(is_dark: bool, high_contrast: bool) -> ThemeBuilder {
+    match (is_dark, high_contrast) {
+        (true, true) => ThemeBuilder::dark_high_contrast(),
+        (true, false) => ThemeBuilder::dark(),
+        (false, true) => ThemeBuilder::light_high_contrast(),
+        (false, false) => ThemeBuilder::light(),
+    }
+}
+
+fn is_high_contrast(builder: &ThemeBuilder) -> bool {
+    matches!(
+        builder.palette,
+        CosmicPalette::HighContrastDark(_) | CosmicPalette::HighContrastLight(_)
+    )
+}
+
+/// Switches a theme builder to the high or normal contrast palette of its mode.
+///
+/// Tints and custom backgrounds lower the contrast of the high contrast palette, so they
+/// are cleared when it is enabled. The accent is kept.
+fn set_high_contrast(builder: &mut ThemeBuilder, is_dark: bool, enabled: bool) {
+    builder.palette = baseline_builder(is_dark, enabled).palette;
+
+    if enabled {
+        builder.text_tint = None;
+        builder.neutral_tint = None;
+        builder.bg_color = None;
+        builder.primary_container_bg = None;
+    }
+}
+
+/// Reads the theme mode, along with its config.
+fn load_theme_mode() -> (Option<Config>, ThemeMode) {
+    let theme_mode_config = ThemeMode::config().ok();
+    let theme_mode = theme_mode_config
+        .as_ref()
+        .map(|c| match ThemeMode::get_entry(c) {
+            Ok(t) => t,
+            Err((errors, t)) => {
+                for e in errors {
+                    tracing::error!("{e}");
+                }
+                t
+            }
+        })
+        .unwrap_or_default();
+
+    (theme_mode_config, theme_mode)
+}
+
+/// Reads the theme builder of a color mode, along with its config.
+fn load_theme_builder(is_dark: bool) -> (Option<Config>, ThemeBuilder) {
+    let theme_builder_config = if is_dark {
+        ThemeBuilder::dark_config()
+    } else {
+        ThemeBuilder::light_config()
+    }
+    .ok();
+    let theme_builder = theme_builder_config.as_ref().map_or_else(
+        || {
+            if is_dark {
+                ThemeBuilder::dark()
+            } else {
+                ThemeBuilder::light()
+            }
+        },
+        |c| match ThemeBuilder::get_entry(c) {
+            Ok(t) => t,
+            Err((errors, t)) => {
+                for e in errors {
+                    tracing::error!("{e}");
+                }
+                t
+            }
+        },
+    );
+
+    (theme_builder_config, theme_builder)
+}
+
+/// Reads the toolkit settings, along with their config.
+fn load_toolkit() -> (Option<Config>, CosmicTk) {
+    let tk_config = CosmicTk::config().ok();
+    let tk = match tk_config.as_ref().map(CosmicTk::get_entry) {
+        Some(Ok(c)) => c,
+        Some(Err((errs, c))) => {
+            for err in errs {
+                tracing::error!(?err, "Error loading CosmicTk");
+            }
+            c
+        }
+        None => CosmicTk::default(),
+    };
+
+    (tk_config, tk)
+}
+
+/// Writes a theme builder, and a theme built from it, to the configs of a color mode.
+///
+/// The stored selection color of the mode is applied to the theme, and nothing is written
+/// if the theme is invalid.
+fn write_theme_config(
+    builder: &ThemeBuilder,
+    mut theme: Theme,
+    is_dark: bool,
+) -> Result<(), ThemeWriteError> {
+    if let Some(color) = config::stored_selection_color(is_dark) {
+        set_selection_color(&mut theme, color);
+    }
+
+    if !theme_is_valid(&theme) {
+        return Err(ThemeWriteError::Invalid);
+    }
+
+    let (builder_config, theme_config) = if is_dark {
+        (ThemeBuilder::dark_config(), Theme::dark_config())
+    } else {
+        (ThemeBuilder::light_config(), Theme::light_config())
+    };
+
+    builder_config
+        .and_then(|config| builder.write_entry(&config))
+        .map_err(ThemeWriteError::Config)?;
+
+    theme_config
+        .and_then(|config| theme.write_entry(&config))
+        .map_err(ThemeWriteError::Config)
+}
+
+/// Overrides the accent-derived selection highlight of a built theme.
+fn set_selection_color(theme: &mut Theme, color: Srgb) {
+    let text = selection_text_color(color);
+    theme.accent.selected = Srgba::new(color.red, color.green, color.blue, 1.0);
+    theme.accent.selected_text = Srgba::new(text.red, text.green, text.blue, 1.0);
+}
+
+/// Black or white, whichever is more legible on the selection color.
+fn selection_text_color(selection: Srgb) -> Srgb {
+    let black = Srgb::new(0.0, 0.0, 0.0);
+    let white = Srgb::new(1.0, 1.0, 1.0);
+
+    if contrast_ratio(black, selection) > contrast_ratio(white, selection) {
+        black
+    } else {
+        white
+    }
+}
+
+/// Checks that a built theme is safe to hand to the rest of the desktop.
+fn theme_is_valid(theme: &Theme) -> bool {
+    let colors = [
+        theme.background.base,
+        theme.background.on,
+        theme.primary.base,
+        theme.primary.on,
+        theme.secondary.base,
+        theme.secondary.on,
+        theme.accent.base,
+        theme.accent.on,
+        theme.success.base,
+        theme.warning.base,
+        theme.destructive.base,
+    ];
+
+    let colors_valid = colors.iter().all(|c| {
+        [c.red, c.green, c.blue, c.alpha]
+            .iter()
+            .all(|v| v.is_finite() && (0.0..=1.0).contains(v))
+    });
+
+    let radii = theme.corner_radii;
+    let radii_valid = [
+        radii.radius_0,
+        radii.radius_xs,
+        radii.radius_s,
+        radii.radius_m,
+        radii.radius_l,
+        radii.radius_xl,
+    ]
+    .iter()
+    .flatten()
+    .all(|r| r.is_finite() && *r >= 0.0);
+
+    colors_valid && radii_valid
+}
+
+/// The border radius of a floating panel or dock.
+///
+/// Panels take a single radius for all corners, so the top left corner of the
+/// extra large radius is used, even when the theme's corners differ.
+fn panel_radius(roundness: Roundness) -> u32 {
+    let radii: CornerRadii = roundness.into();
+    radii.radius_xl[Corner::TopLeft as usize] as u32
+}
+
+/// Whether the panel or dock floats, in which case its corners follow the theme roundness.
+fn panel_is_floating() -> bool {
+    ["Panel", "Dock"].into_iter().any(|name| {
+        CosmicPanelConfig::cosmic_config(name)
+            .ok()
+            .and_then(|config| CosmicPanelConfig::get_entry(&config).ok())
+            .is_some_and(|config| {
+                config.name == name && (config.anchor_gap || !config.expand_to_edges)
+            })
+    })
+}
+
+/// Find all icon themes available on the system, along with their preview handles.
+///
+/// Themes are sent to the page in batches as they are found, if a sender is given.
+/// Themes which are unchanged since the previous scan reuse its handles from `memo`.
+async fn fetch_icon_themes(
+    excludes: Vec<PathBuf>,
+    progress: Option<PageSender>,
+    memo: Arc<Mutex<IconPreviewMemo>>,
+) -> (IconThemes, IconHandles) {
+    let mut icon_themes = Vec::new();
+    let mut icon_handles = Vec::new();
+    let mut batch: (IconThemes, IconHandles) = Default::default();
+
+    let cache = tokio::task::spawn_blocking(IconCache::load)
+        .await
+        .unwrap_or_default();
+    // Rebuilt from the themes found, so that removed themes are dropped.
+    let mut new_cache = IconCache::default();
+    let mut new_memo = IconPreviewMemo::new();
+
+    for theme in find_icon_themes(&excludes).await {
+        let memoized = theme.index_modified.and_then(|modified| {
+            memo.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(&theme.id)
+                .filter(|(memo_modified, ..)| *memo_modified == modified)
+                .map(|(_, paths, handles)| (paths.clone(), handles.clone()))
+        });
+
+        let (paths, handles) = if let Some(memoized) = memoized {
+            memoized
+        } else {
+            let cached = theme
+                .index_modified
+                .and_then(|modified| cache.get(&theme.id, modified))
+                .map(<[_]>::to_vec);
+
+            let paths = if let Some(paths) = cached {
+                paths
+            } else {
+                let id = theme.id.clone();
+                let inherits = theme.inherits.clone();
+                // `icon::from_name` may perform blocking I/O
+                match tokio::task::spawn_blocking(|| preview_paths(id, inherits, false)).await {
+                    Ok(paths) => paths,
+                    Err(_) => continue,
+                }
+            };
+
+            let handles = preview_handles_from_paths(&paths, false);
+            (paths, handles)
+        };
+
+        if let Some(modified) = theme.index_modified {
+            new_cache.insert(theme.id.clone(), modified, paths.clone());
+            new_memo.insert(theme.id.clone(), (modified, paths, handles.clone()));
+        }
+
+        if let Some(progress) = progress.as_ref() {
+            batch.0.push(theme.clone());
+            batch.1.push(handles.clone());
+
+            if batch.0.len() == ICON_SCAN_BATCH {
+                let found = Message::IconThemesFound(std::mem::take(&mut batch));
+                _ = progress
+                    .send(crate::pages::Message::Appearance(found))
+                    .await;
+            }
+        }
+
+        icon_handles.push(handles);
+        icon_themes.push(theme);
+    }
+
+    *memo.lock().unwrap_or_else(PoisonError::into_inner) = new_memo;
+
+    tokio::task::spawn_blocking(move || {
+        if let Err(why) = new_cache.save() {
+            tracing::error!(?why, "failed to write the icon preview cache");
+        }
+    });
+
+    (icon_themes, icon_handles)
+}
+
+/// The icon directory in the user's data directory.
+fn user_icon_dir() -> Option<PathBuf> {
+    std::env::var("XDG_DATA_HOME")
+        .ok()
+        .and_then(|value| {
+            if value.is_empty() {
+                None
+            } else {
+                Some(PathBuf::from(value))
+            }
+        })
+        .or_else(|| dirs::home_dir().map(|dir| dir.join(".local/share")))
+        .map(|dir| dir.join("icons"))
+}
+
+/// The directories which icon and cursor themes are installed to, outside of the
+/// excluded directories. The user's directory comes last.
+fn icon_dirs(excludes: &[PathBuf]) -> Vec<PathBuf> {
+    let xdg_data_dirs = std::env::var("XDG_DATA_DIRS").ok();
+
+    let xdg_data_dirs = xdg_data_dirs
+        .as_deref()
+        // Default from the XDG Base Directory Specification
+        .or(Some("/usr/local/share/:/usr/share/"))
+        .into_iter()
+        .flat_map(|arg| std::env::split_paths(arg).map(|dir| dir.join("icons")));
+
+    // Compared lexically, since resolving paths on a slow mount is what exclusions avoid.
+    xdg_data_dirs
+        .chain(user_icon_dir())
+        .filter(|dir| !excludes.iter().any(|excluded| dir.starts_with(excluded)))
+        .collect()
+}
+
+/// Find all icon themes available on the system, outside of the excluded directories.
+///
+/// A theme installed in several directories is found once, preferring the user's copy.
+async fn find_icon_themes(excludes: &[PathBuf]) -> BTreeSet<IconTheme> {
+    // Preferred locales for localized keys, from most to least specific.
+    let locales: Vec<String> = DesktopLanguageRequester::requested_languages()
+        .iter()
+        .flat_map(|lang| {
+            [
+                lang.to_string().replace('-', "_"),
+                lang.language.as_str().to_owned(),
+            ]
+        })
+        .collect();
+
+    find_icon_themes_in(icon_dirs(excludes), &locales).await
+}
+
+/// Find the icon themes in icon directories, where themes in later directories replace
+/// themes of the same ID in earlier ones.
+async fn find_icon_themes_in(icon_dirs: Vec<PathBuf>, locales: &[String]) -> BTreeSet<IconTheme> {
+    let mut icon_themes: BTreeMap<String, IconTheme> = BTreeMap::new();
+    // Paths of themes by ID, for resolving the directories of inherited themes.
+    let mut theme_paths: BTreeMap<String, PathBuf> = BTreeMap::new();
+
+    for icon_dir in icon_dirs {
+        let Ok(mut read_dir) = tokio::fs::read_dir(&icon_dir).await else {
+            continue;
+        };
+
+        while let Ok(Some(entry)) = read_dir.next_entry().await {
+            let Ok(path) = tokio::fs::canonicalize(entry.path()).await else {
+                continue;
+            };
+
+            let Some(id) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+
+            let manifest = path.join("index.theme");
+            let installed = tokio::fs::metadata(&path)
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            let index_modified = tokio::fs::metadata(&manifest)
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok();
+
+            // Fails if the directory has no manifest, in which case it is not an icon theme.
+            let Ok(contents) = tokio::fs::read_to_string(&manifest).await else {
+                continue;
+            };
+
+            let Some(index) = parse_icon_theme_index(&contents, locales) else {
+                continue;
+            };
+
+            let dir_name = |path: &Path| {
+                path.iter()
+                    .last()
+                    .and_then(|os| os.to_str().map(ToOwned::to_owned))
+            };
+
+            let mut valid_dirs: Vec<String> = Vec::new();
+            for fallback in index.inherits {
+                let fallback = theme_paths
+                    .get(&fallback)
+                    .and_then(|path| dir_name(path.as_path()))
+                    .unwrap_or(fallback);
+                if !valid_dirs.contains(&fallback) {
+                    valid_dirs.push(fallback);
+                }
+            }
+
+            // Name of the directory theme was found in (e.g. Pop for Pop)
+            valid_dirs.push(dir_name(&path).unwrap_or_else(|| index.name.clone()));
+            theme_paths.insert(id.clone(), path);
+
+            // Cursor themes have no icon directories, and the fallback theme is only
+            // meant to be inherited from.
+            if !index.has_directories || id == FALLBACK_ICON_THEME {
+                continue;
+            }
+
+            icon_themes.insert(
+                id.clone(),
+                IconTheme {
+                    id,
+                    name: index.name,
+                    comment: index.comment,
+                    installed,
+                    index_modified,
+                    inherits: valid_dirs,
+                },
+            );
+        }
+    }
+
+    icon_themes.into_values().collect()
+}
+
+/// The `[Icon Theme]` section of the `index.theme` of an icon theme.
+#[derive(Debug, Default, PartialEq)]
+struct IconThemeIndex {
+    name: String,
+    /// The comment in the most preferred locale.
+    comment: Option<String>,
+    /// Names of the themes inherited from, in the order they are listed.
+    inherits: Vec<String>,
+    /// Whether the theme lists any icon directories.
+    has_directories: bool,
+}
+
+/// Parses the `index.theme` of an icon theme, or returns `None` if the theme is hidden or
+/// has no name.
+///
+/// Only keys of the `[Icon Theme]` section are read, as other sections describe the
+/// theme's directories and may reuse keys. A repeated key keeps its last value, as GTK's
+/// key file parser does, except for `Inherits`, whose keys are all followed.
+fn parse_icon_theme_index(contents: &str, locales: &[String]) -> Option<IconThemeIndex> {
+    let mut name = None;
+    let mut comment: Option<(usize, String)> = None;
+    let mut inherits: Vec<String> = Vec::new();
+    let mut has_directories = false;
+    let mut in_icon_theme = false;
+
+    for line in contents.lines() {
+        if line.starts_with('[') {
+            in_icon_theme = line.trim() == "[Icon Theme]";
+            continue;
+        }
+
+        if !in_icon_theme {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        match key {
+            "Hidden" | "NoDisplay" if value.eq_ignore_ascii_case("true") => return None,
+            "Name" => name = Some(value.to_owned()),
+            "Directories" => has_directories = !value.is_empty(),
+            "Inherits" => {
+                for fallback in value.split(',').map(str::trim) {
+                    if !fallback.is_empty() && !inherits.iter().any(|known| known == fallback) {
+                        inherits.push(fallback.to_owned());
+                    }
+                }
+            }
+            _ => {
+                let rank = key
+                    .strip_prefix("Comment")
+                    .and_then(|locale| locale_rank(locale, locales));
+
+                if let Some(rank) = rank {
+                    if comment.as_ref().map_or(true, |(best, _)| rank <= *best) {
+                        comment = Some((rank, value.to_owned()));
+                    }
+                }
+            }
+        }
+    }
+
+    Some(IconThemeIndex {
+        name: name.filter(|name| !name.is_empty())?,
+        comment: comment.map(|(_, comment)| comment),
+        inherits,
+        has_directories,
+    })
+}
+
+/// Prints the available icon themes, look profiles, and snapshots for scripting, one per
+/// line.
+///
+/// Each line holds four tab-separated fields: the kind of entry, its ID, its name, and
+/// `current` if it is the current icon theme or the active look profile, which is empty
+/// otherwise.
+pub async fn print_theme_list() {
+    let current = CosmicTk::config()
+        .ok()
+        .map(|config| match CosmicTk::get_entry(&config) {
+            Ok(tk) | Err((_, tk)) => tk,
+        })
+        .unwrap_or_default()
+        .icon_theme;
+
+    let config = PageConfig::new();
+    let excludes = config.icon_scan_excludes().to_vec();
+
+    for theme in find_icon_themes(&excludes).await {
+        let line = theme_list_line("icon-theme", &theme.id, &theme.name, theme.id == current);
+        println!("{line}");
+    }
+
+    for name in profile::list() {
+        let current = config.active_look_profile() == Some(name.as_str());
+        println!("{}", theme_list_line("look-profile", &name, &name, current));
+    }
+
+    // Snapshots record past states, so none of them is ever current.
+    for id in snapshot::list() {
+        let line = theme_list_line("snapshot", &id.to_string(), &snapshot::timestamp(id), false);
+        println!("{line}");
+    }
+}
+
+fn theme_list_line(kind: &str, id: &str, name: &str, current: bool) -> String {
+    let marker = if current { "current" } else { "" };
+    format!("{kind}\t{id}\t{name}\t{marker}")
+}
+
+/// Applies a theme file to the theme config, reading it from stdin if the path is `-`.
+///
+/// A single theme is applied to the current color mode, while a bundle applies to both.
+///
+/// # Errors
+///
+/// Returns an error if the theme could not be read, parsed, validated, or written.
+pub fn apply_theme(path: &std::path::Path) -> std::io::Result<()> {
+    use std::io::{Error, ErrorKind, Read};
+
+    let contents = if path == std::path::Path::new("-") {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        contents
+    } else {
+        std::fs::read_to_string(path)?
+    };
+
+    let theme = ImportedTheme::parse(&contents)
+        .map_err(|why| Error::new(ErrorKind::InvalidData, why.message()))?;
+
+    let modes = if let ImportedTheme::Bundle(_) = theme {
+        vec![false, true]
+    } else {
+        let is_dark = ThemeMode::config()
+            .ok()
+            .map(|config| match ThemeMode::get_entry(&config) {
+                Ok(mode) | Err((_, mode)) => mode,
+            })
+            .unwrap_or_default()
+            .is_dark;
+        vec![is_dark]
+    };
+
+    for is_dark in modes {
+        match write_theme_builder(&theme.builder(is_dark), is_dark) {
+            Ok(()) => (),
+            Err(ThemeWriteError::Invalid) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "theme has invalid colors or sizes",
+                ));
+            }
+            Err(ThemeWriteError::Config(why)) => {
+                return Err(Error::new(ErrorKind::Other, why.to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Ranks the locale suffix of a localized key, such as `[de]`, or none for the
+/// unlocalized key.
+///
+/// Lower ranks are preferred, following the order of `locales`, and the unlocalized key
+/// ranks last. Locales which are not preferred are not ranked.
+fn locale_rank(suffix: &str, locales: &[String]) -> Option<usize> {
+    if suffix.is_empty() {
+        return Some(locales.len());
+    }
+
+    let locale = suffix.strip_prefix('[')?.strip_suffix(']')?;
+    locales.iter().position(|l| l == locale)
+}
+
+/// Set the preferred icon theme for GNOME/GTK applications.
+async fn set_gnome_icon_theme(theme: String) -> Result<(), String> {
+    let status = tokio::process::Command::new("gsettings")
+        .args([
+            "set",
+            "org.gnome.desktop.interface",
+            "icon-theme",
+            theme.as_str(),
+        ])
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gsettings exited with {status}"))
+    }
+}
+
+/// Sets the GNOME icon theme, and reports the result back to the page.
+fn gnome_icon_theme_command(theme: String) -> Command<app::Message> {
+    Command::perform(set_gnome_icon_theme(theme), |result| {
+        crate::Message::PageMessage(crate::pages::Message::Appearance(
+            Message::IconThemeGnomeResult(result),
+        ))
+    })
+}
+
+/// Set whether GNOME/GTK applications, and others which follow the XDG color scheme,
+/// prefer a dark or light style.
+async fn set_gnome_color_scheme(is_dark: bool) -> Result<(), String> {
+    let scheme = if is_dark {
+        "prefer-dark"
+    } else {
+        "prefer-light"
+    };
+    let status = tokio::process::Command::new("gsettings")
+        .args(["set", "org.gnome.desktop.interface", "color-scheme", scheme])
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("gsettings exited with {status}"))
+    }
+}
+
+/// Sets the GNOME color scheme, and reports the result back to the page.
+fn gnome_color_scheme_command(is_dark: bool) -> Command<app::Message> {
+    Command::perform(set_gnome_color_scheme(is_dark), |result| {
+        crate::Message::PageMessage(crate::pages::Message::Appearance(
+            Message::ColorSchemeGnomeResult(result),
+        ))
+    })
+}
+
+/// A dropdown of names, such as font families, which selects by name.
+fn name_dropdown<'a>(
+    names: &'a Arc<[String]>,
+    active: Option<&str>,
+    on_select: fn(String) -> Message,
+) -> Element<'a, Message> {
+    let selected = active.and_then(|active| names.iter().position(|name| name == active));
+    let names_ = names.clone();
+
+    dropdown(names, selected, move |i| on_select(names_[i].clone())).into()
+}
+
+/// Sample text in a font family, or in the default font if none was chosen.
+fn font_sample<'a>(sample: &'a str, family: Option<&str>) -> Element<'a, Message> {
+    let mut sample = text(sample).size(16);
+    if let Some(family) = family {
+        sample = sample.font(family_font(family));
+    }
+
+    sample.into()
+}
+
+/// A font of the given family, for samples.
+///
+/// Family names of fonts must be `'static`, so each distinct name is leaked once.
+fn family_font(family: &str) -> cosmic::iced::Font {
+    static NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let mut names = NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    let name = match names.get(family) {
+        Some(name) => *name,
+        None => {
+            let name: &'static str = Box::leak(family.to_owned().into_boxed_str());
+            names.insert(name);
+            name
+        }
+    };
+
+    cosmic::iced::Font {
+        family: cosmic::iced::font::Family::Name(name),
+        ..cosmic::iced::Font::DEFAULT
+    }
+}
+
+/// Sets a GNOME font, and reports the result back to the page.
+fn gnome_font_command(kind: FontKind, family: String) -> Command<app::Message> {
+    Command::perform(font::set_gnome_font(kind, family), |result| {
+        crate::Message::PageMessage(crate::pages::Message::Appearance(Message::GnomeFontResult(
+            result,
+        )))
+    })
+}
+
+/// A cursor theme's name beneath its default cursor at each preview size.
+fn cursor_theme_button(theme: &CursorTheme, id: usize, selected: bool) -> Element<'_, Message> {
+    let previews = theme
+        .previews
+        .iter()
+        .zip(cursor::PREVIEW_SIZES)
+        .fold(
+            row::with_capacity(cursor::PREVIEW_SIZES.len()),
+            |row, (handle, size)| {
+                row.push(
+                    cosmic::widget::image(handle.clone())
+                        .width(Length::Fixed(size as f32))
+                        .height(Length::Fixed(size as f32)),
+                )
+            },
+        )
+        .spacing(8)
+        .height(Length::Fixed(cursor::PREVIEW_SIZES[1] as f32))
+        .align_items(cosmic::iced_core::Alignment::End);
+
+    let name = &theme.name;
+    button(
+        cosmic::widget::column::with_capacity(2)
+            .push(previews)
+            .push(text::body(if name.len() > ICON_NAME_TRUNC {
+                format!("{name:.ICON_NAME_TRUNC$}...")
+            } else {
+                name.clone()
+            }))
+            .spacing(8)
+            .align_items(cosmic::iced_core::Alignment::Center),
+    )
+    .selected(selected)
+    .style(button::Style::Image)
+    .padding(8)
+    .on_press(Message::CursorTheme(id))
+    .into()
+}
+
+/// Icons shown in icon theme previews, along with their symbolic variants.
+const PREVIEW_ICONS: [(&str, &str); ICON_PREV_N] = [
+    ("folder", "folder-symbolic"),
+    ("user-home", "user-home-symbolic"),
+    ("text-x-generic", "text-x-generic-symbolic"),
+    ("image-x-generic", "images-x-generic-symbolic"),
+    ("audio-x-generic", "audio-x-generic-symbolic"),
+    ("video-x-generic", "video-x-generic-symbolic"),
+];
+
+/// Held while the default icon theme is temporarily replaced to resolve previews.
+static ICON_DEFAULT_LOCK: Mutex<()> = Mutex::new(());
+
+/// An icon previewed for an icon theme.
+#[derive(Clone, Debug)]
+struct PreviewIcon {
+    handle: icon::Handle,
+    /// Whether the theme lacks the icon, so that it is shown from the default theme.
+    fallback: bool,
+}
+
+/// Generate [icon::Handle]s to use for icon theme previews.
+///
+/// Symbolic icons are preferred over full color icons if `symbolic` is set.
+fn preview_handles(theme: String, inherits: Vec<String>, symbolic: bool) -> PreviewHandles {
+    preview_handles_from_paths(&preview_paths(theme, inherits, symbolic), symbolic)
+}
+
+/// Resolve the paths of the icons previewed for a theme.
+fn preview_paths(theme: String, inherits: Vec<String>, symbolic: bool) -> Vec<Option<PathBuf>> {
+    // The default icon theme is global, so concurrent lookups must not interleave.
+    let _guard = ICON_DEFAULT_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    // Cache current default and set icon theme as a temporary default
+    let default = cosmic::icon_theme::default();
+    cosmic::icon_theme::set_default(theme);
+
+    // Evaluate paths with the temporary theme
+    let paths = PREVIEW_ICONS
+        .iter()
+        .map(|&(regular, alternate)| {
+            if symbolic {
+                icon_path(alternate, regular, &inherits)
+            } else {
+                icon_path(regular, alternate, &inherits)
+            }
+        })
+        .collect();
+
+    // Reset default icon theme.
+    cosmic::icon_theme::set_default(default);
+    paths
+}
+
+/// Create the preview handles from resolved icon paths, falling back to icons by name.
+fn preview_handles_from_paths(paths: &[Option<PathBuf>], symbolic: bool) -> PreviewHandles {
+    std::array::from_fn(|i| {
+        let (regular, alternate) = PREVIEW_ICONS[i];
+        match paths.get(i) {
+            Some(Some(path)) => PreviewIcon {
+                handle: icon::from_path(path.clone()),
+                fallback: false,
+            },
+            _ => PreviewIcon {
+                handle: icon::from_name(if symbolic { alternate } else { regular })
+                    .size(ICON_THUMB_SIZE)
+                    .handle(),
+                fallback: true,
+            },
+        }
+    })
+}
+
+/// Evaluate the path of an icon for a specific theme.
+///
+/// `alternate` is a fallback icon name such as a symbolic variant.
+///
+/// `valid_dirs` should be a slice of directories from which we consider an icon to be valid. Valid
+/// directories would usually be inherited themes as well as the actual theme's location.
+fn icon_path(icon_name: &str, alternate: &str, valid_dirs: &[String]) -> Option<PathBuf> {
+    ICON_TRY_SIZES
+        .iter()
+        .zip(std::iter::repeat(icon_name).take(ICON_TRY_SIZES.len()))
+        // Try fallback icon name after the default
+        .chain(
+            ICON_TRY_SIZES
+                .iter()
+                .zip(std::iter::repeat(alternate))
+                .take(ICON_TRY_SIZES.len()),
+        )
+        .find_map(|(&size, name)| {
+            icon::from_name(name)
+                // Set the size on the handle to evaluate the correct icon
+                .size(size)
+                // Get the path to the icon for the currently set theme.
+                // Without the exact path, the handles will all resolve to icons from the same theme in
+                // [`icon_theme_button`] rather than the icons for each different theme
+                .path()
+                // `libcosmic` should always return a path if the default theme is installed
+                // The returned path has to be verified as an icon from the set theme or an
+                // inherited theme
+                .and_then(|path| {
+                    let mut theme_dir = &*path;
+                    while let Some(parent) = theme_dir.parent() {
+                        if parent.ends_with("icons") {
+                            break;
+                        }
+                        theme_dir = parent;
+                    }
+
+                    if let Some(dir_name) =
+                        theme_dir.iter().last().and_then(std::ffi::OsStr::to_str)
+                    {
+                        valid_dirs
+                            .iter()
+                            .any(|valid| dir_name == valid)
+                            .then_some(path)
+                    } else {
+                        None
+                    }
+                })
+        })
+}
+
+/// A previewed icon, outlined in the warning color if diagnostics are shown and the
+/// theme lacks it.
+fn preview_icon(preview: &PreviewIcon, size: u16, diagnostics: bool) -> Element<'static, Message> {
+    let icon = preview.handle.clone().icon().size(size);
+    if !(diagnostics && preview.fallback) {
+        return icon.into();
+    }
+
+    let theme = cosmic::theme::active();
+    let warning = theme.cosmic().warning.base;
+    let background = Color::from(warning.with_alpha(0.25));
+    let border = Color::from(warning);
+
+    container(icon)
+        .style(cosmic::theme::Container::custom(move |_| {
+            container::Appearance {
+                background: Some(Background::Color(background)),
+                border: cosmic::iced_core::Border {
+                    color: border,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+/// Button with a preview of the icon theme.
+fn icon_theme_button(
+    name: &str,
+    comment: Option<&str>,
+    handles: &[PreviewIcon],
+    id: usize,
+    selected: bool,
+    size: u16,
+    diagnostics: bool,
+) -> Element<'static, Message> {
+    let theme = cosmic::theme::active();
+    let theme = theme.cosmic();
+    let background = Background::Color(theme.palette.neutral_4.into());
+    let preview_icon = |preview: &PreviewIcon| preview_icon(preview, size, diagnostics);
+
+    let content = cosmic::widget::column()
+        .push(
+            cosmic::widget::button::custom_image_button(
+                cosmic::widget::column::with_children(vec![
+                    cosmic::widget::row()
+                        .extend(handles.iter().take(ICON_PREV_ROW).map(preview_icon))
+                        .spacing(theme.space_xxs())
+                        .into(),
+                    cosmic::widget::row()
+                        .extend(handles.iter().skip(ICON_PREV_ROW).map(preview_icon))
+                        .spacing(theme.space_xxs())
+                        .into(),
+                ])
+                .spacing(theme.space_xs()),
+                None,
+            )
+            .on_press(Message::IconTheme(id))
+            .selected(selected)
+            .padding(theme.space_xxs())
+            // Image button's style mostly works, but it needs a background to fit the design
+            .style(button::Style::Custom {
+                active: Box::new(move |focused, theme| {
+                    let mut appearance = <cosmic::theme::Theme as button::StyleSheet>::active(
+                        theme,
+                        focused,
+                        selected,
+                        &cosmic::theme::Button::Image,
+                    );
+                    appearance.background = Some(background);
+                    appearance
+                }),
+                disabled: Box::new(move |theme| {
+                    let mut appearance = <cosmic::theme::Theme as button::StyleSheet>::disabled(
+                        theme,
+                        &cosmic::theme::Button::Image,
+                    );
+                    appearance.background = Some(background);
+                    appearance
+                }),
+                hovered: Box::new(move |focused, theme| {
+                    let mut appearance = <cosmic::theme::Theme as button::StyleSheet>::hovered(
+                        theme,
+                        focused,
+                        selected,
+                        &cosmic::theme::Button::Image,
+                    );
+                    appearance.background = Some(background);
+                    appearance
+                }),
+                pressed: Box::new(move |focused, theme| {
+                    let mut appearance = <cosmic::theme::Theme as button::StyleSheet>::pressed(
+                        theme,
+                        focused,
+                        selected,
+                        &cosmic::theme::Button::Image,
+                    );
+                    appearance.background = Some(background);
+                    appearance
+                }),
+            }),
+        )
+        .push(
+            text(if name.len() > ICON_NAME_TRUNC {
+                format!("{name:.ICON_NAME_TRUNC$}...")
+            } else {
+                name.into()
+            })
+            .width(Length::Fixed((size * 3) as _)),
+        )
+        .spacing(theme.space_xs());
+
+    if let Some(comment) = comment {
+        cosmic::iced::widget::tooltip(
+            content,
+            text(comment.to_owned()),
+            cosmic::iced::widget::tooltip::Position::Bottom,
+        )
+        .into()
+    } else {
+        content.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_accent_matches_exactly() {
+        let theme = Theme::dark_default();
+        assert!(is_palette_accent(theme.palette.accent_blue.color, &theme));
+    }
+
+    #[test]
+    fn palette_accent_matches_with_different_opacity() {
+        let mut theme = Theme::dark_default();
+        let blue = theme.palette.accent_blue.color;
+        theme.palette.accent_blue.alpha = 0.5;
+        assert!(is_palette_accent(blue, &theme));
+    }
+
+    #[test]
+    fn custom_accent_does_not_match() {
+        let theme = Theme::dark_default();
+        assert!(!is_palette_accent(Srgb::new(0.123, 0.456, 0.789), &theme));
+    }
+
+    #[test]
+    fn dark_mode_keeps_icon_state() {
+        let mut page = in_memory_page();
+        // Previewing keeps the mode switch from being written.
+        page.preview = Some(page.theme_builder.clone());
+
+        page.tk.icon_theme = "Pop".to_owned();
+        page.icon_themes = vec![icon_theme("Pop")];
+        page.icon_handles = vec![preview_handles()];
+        page.icon_theme_active = Some(0);
+        page.icon_theme_filter = "po".to_owned();
+
+        let _ = page.update(Message::DarkMode(!page.theme_mode.is_dark));
+
+        assert_eq!(page.icon_themes.len(), 1);
+        assert_eq!(page.icon_themes[0].id, "Pop");
+        assert_eq!(page.icon_handles.len(), 1);
+        assert_eq!(page.icon_theme_active, Some(0));
+        assert_eq!(page.icon_theme_filter, "po");
+    }
+
+    #[test]
+    fn missing_imported_icon_theme_keeps_current_theme() {
+        let mut page = in_memory_page();
+        page.tk.icon_theme = "Pop".to_owned();
+        page.icon_themes = vec![icon_theme("Pop")];
+        page.icon_handles = vec![preview_handles()];
+        page.icon_theme_active = Some(0);
+
+        // A theme which is not known yet is looked for again before giving up.
+        let _ = page.update(Message::ImportIconTheme(Some("Missing".to_owned())));
+        assert_eq!(page.pending_icon_theme.as_deref(), Some("Missing"));
+        assert!(page.icon_refresh_in_flight);
+
+        let _ = page.update(Message::IconThemesScanned((
+            vec![icon_theme("Pop")],
+            vec![preview_handles()],
+        )));
+
+        assert_eq!(page.pending_icon_theme, None);
+        assert!(!page.icon_refresh_in_flight);
+        assert_eq!(page.tk.icon_theme, "Pop");
+        assert_eq!(page.icon_theme_active, Some(0));
+    }
+
+    #[test]
+    fn nested_message_is_one_history_entry() {
+        let mut page = in_memory_page();
+        let before = page.theme_builder.clone();
+
+        let _ = page.update(Message::RandomizeAccent);
+
+        assert_eq!(page.undo_history, [before]);
+    }
+
+    #[test]
+    fn edit_without_theme_config_is_still_applied() {
+        let mut page = in_memory_page();
+        let accent = Theme::dark_default().palette.accent_red;
+
+        let _ = page.update(Message::PaletteAccent(accent.into()));
+
+        assert_eq!(page.theme_builder.accent, Some(accent.color));
+        assert_eq!(page.undo_history.len(), 1);
+        assert!(!page.theme_write_pending);
+    }
+
+    #[test]
+    fn accent_focus_moves_between_swatches() {
+        assert_eq!(next_accent_swatch(&accent_swatch_id(2), 5, 1), Some(3));
+        assert_eq!(next_accent_swatch(&accent_swatch_id(2), 5, -1), Some(1));
+    }
+
+    #[test]
+    fn accent_focus_stops_at_the_ends() {
+        assert_eq!(next_accent_swatch(&accent_swatch_id(0), 5, -1), Some(0));
+        assert_eq!(next_accent_swatch(&accent_swatch_id(4), 5, 1), Some(4));
+    }
+
+    #[test]
+    fn accent_focus_ignores_other_widgets() {
+        let other = cosmic::widget::Id::new("import-button");
+        assert_eq!(next_accent_swatch(&other, 5, 1), None);
+        assert_eq!(next_accent_swatch(&accent_swatch_id(7), 5, 1), None);
+    }
+
+    #[test]
+    fn theme_list_lines_have_the_same_fields() {
+        let current = theme_list_line("look-profile", "Work", "Work", true);
+        let other = theme_list_line("snapshot", "1700000000000", "Tue Nov 14", false);
+
+        assert_eq!(current, "look-profile\tWork\tWork\tcurrent");
+        assert_eq!(other, "snapshot\t1700000000000\tTue Nov 14\t");
+        assert_eq!(current.split('\t').count(), other.split('\t').count());
+    }
+
+    #[test]
+    fn export_over_existing_file_waits_for_confirmation() {
+        let path =
+            std::env::temp_dir().join(format!("cosmic-settings-export-{}.txt", std::process::id()));
+        std::fs::write(&path, "old").unwrap();
+
+        let mut page = in_memory_page();
+
+        let _ = page.export(path.clone(), "diagnostics".to_owned(), false);
+        assert!(matches!(
+            page.context_view,
+            Some(ContextView::ConfirmOverwrite)
+        ));
+        assert!(page.pending_export.is_some());
+
+        let _ = page.update(Message::CancelOverwrite);
+        assert!(page.pending_export.is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old");
+
+        page.config.set_overwrite_without_confirm(true).unwrap();
+        let _ = page.export(path.clone(), "diagnostics".to_owned(), false);
+        assert!(page.pending_export.is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn export_to_new_file_is_not_confirmed() {
+        let path = std::env::temp_dir().join(format!(
+            "cosmic-settings-export-new-{}.txt",
+            std::process::id()
+        ));
+        _ = std::fs::remove_file(&path);
+
+        let mut page = in_memory_page();
+
+        let _ = page.export(path, "diagnostics".to_owned(), false);
+        assert!(page.pending_export.is_none());
+        assert!(page.context_view.is_none());
+    }
+
+    #[test]
+    fn preview_keeps_overrides_in_memory() {
+        let mut page = in_memory_page();
+
+        let _ = page.update(Message::PreviewMode(true));
+        let _ = page.update(Message::WindowHintOpacity(0.5));
+        let _ = page.write_theme();
+
+        assert_eq!(page.config.window_hint_opacity(), 1.0);
+        assert_eq!(page.config.selection_color(page.theme_mode.is_dark), None);
+
+        let _ = page.update(Message::PreviewMode(false));
+        assert_eq!(page.window_hint_opacity, 1.0);
+    }
+
+    #[test]
+    fn restore_defaults_while_previewing_keeps_saved_colors() {
+        let mut page = in_memory_page();
+        page.config
+            .add_saved_accent(Srgb::new(1.0, 0.0, 0.0))
+            .unwrap();
+        page.preview = Some(page.theme_builder.clone());
+
+        let _ = page.update(Message::RestoreAllDefaults);
+
+        assert_eq!(page.config.saved_accents().len(), 1);
+    }
+
+    #[test]
+    fn config_forgets_theme_overrides() {
+        let mut config = PageConfig::default();
+        config
+            .set_selection_color(true, Some(Srgb::new(1.0, 0.0, 0.0)))
+            .unwrap();
+        config.set_window_hint_opacity(0.5).unwrap();
+        config.set_interface_font("Fira Sans".to_owned()).unwrap();
+
+        config.clear_theme_overrides().unwrap();
+
+        assert_eq!(config.selection_color(true), None);
+        assert_eq!(config.window_hint_opacity(), 1.0);
+        assert_eq!(config.interface_font(), None);
+    }
+
+    #[test]
+    fn selection_color_is_kept_per_mode() {
+        let mut config = PageConfig::default();
+        let red = Srgb::new(1.0, 0.0, 0.0);
+
+        config.set_selection_color(true, Some(red)).unwrap();
+
+        assert_eq!(config.selection_color(true), Some(red));
+        assert_eq!(config.selection_color(false), None);
+    }
+
+    #[test]
+    fn decoration_accent_is_kept_per_mode() {
+        let mut config = PageConfig::default();
+        let red = Srgb::new(1.0, 0.0, 0.0);
+
+        config.set_decoration_accent(false, Some(red)).unwrap();
+
+        assert_eq!(config.decoration_accent(false), Some(red));
+        assert_eq!(config.decoration_accent(true), None);
+    }
+
+    /// A page which neither reads nor writes the user's settings.
+    fn in_memory_page() -> Page {
+        let theme_mode = ThemeMode::default();
+        let theme_builder = if theme_mode.is_dark {
+            ThemeBuilder::dark()
+        } else {
+            ThemeBuilder::light()
+        };
+
+        Page::with_config(
+            PageConfig::default(),
+            false,
+            (
+                None,
+                theme_mode,
+                None,
+                theme_builder,
+                None,
+                CosmicTk::default(),
+            ),
+        )
+    }
+
+    fn icon_theme(id: &str) -> IconTheme {
+        IconTheme {
+            id: id.to_owned(),
+            name: id.to_owned(),
+            comment: None,
+            installed: None,
+            index_modified: None,
+            inherits: vec![id.to_owned()],
+        }
+    }
+
+    fn preview_handles() -> PreviewHandles {
+        std::array::from_fn(|_| PreviewIcon {
+            handle: icon::from_name("folder").handle(),
+            fallback: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn icon_theme_index_follows_every_inherits_key() {
+        let contents = "[Icon Theme]\nName=Pop\nDirectories=apps\n\
+                        Inherits=Adwaita, hicolor\nInherits=breeze,hicolor\n";
+        let index = parse_icon_theme_index(contents, &[]).unwrap();
+
+        assert_eq!(index.inherits, ["Adwaita", "hicolor", "breeze"]);
+
+        let root =
+            std::env::temp_dir().join(format!("cosmic-settings-inherits-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("Pop")).unwrap();
+        std::fs::write(root.join("Pop").join("index.theme"), contents).unwrap();
+
+        let themes = find_icon_themes_in(vec![root.clone()], &[]).await;
+        _ = std::fs::remove_dir_all(&root);
+
+        let pop = themes.iter().find(|theme| theme.id == "Pop").unwrap();
+        assert_eq!(pop.inherits, ["Adwaita", "hicolor", "breeze", "Pop"]);
+    }
+
+    #[test]
+    fn icon_theme_index_only_reads_icon_theme_section() {
+        let index = parse_icon_theme_index(
+            "Name=Before\n[Icon Theme]\nName=Pop\nComment=Icons\nDirectories=apps\n\
+             [apps]\nName=Apps\nComment=Directory\nHidden=true\nInherits=Other\n",
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            index,
+            IconThemeIndex {
+                name: "Pop".to_owned(),
+                comment: Some("Icons".to_owned()),
+                inherits: Vec::new(),
+                has_directories: true,
+            }
+        );
+    }
+
+    #[test]
+    fn icon_theme_index_keeps_last_name() {
+        let index =
+            parse_icon_theme_index("[Icon Theme]\nName=Old\nName=New\nDirectories=apps\n", &[])
+                .unwrap();
+
+        assert_eq!(index.name, "New");
+    }
+
+    #[test]
+    fn icon_theme_index_skips_hidden_themes() {
+        for key in ["Hidden=true", "Hidden=True", "NoDisplay=TRUE"] {
+            let contents = format!("[Icon Theme]\nName=Pop\nDirectories=apps\n{key}\n");
+            assert_eq!(parse_icon_theme_index(&contents, &[]), None, "{key}");
+        }
+
+        let visible = "[Icon Theme]\nName=Pop\nDirectories=apps\nHidden=false\n";
+        assert!(parse_icon_theme_index(visible, &[]).is_some());
+    }
+
+    #[test]
+    fn icon_theme_index_without_directories() {
+        let index = parse_icon_theme_index("[Icon Theme]\nName=Cursors\n", &[]).unwrap();
+        assert!(!index.has_directories);
+
+        assert_eq!(
+            parse_icon_theme_index("[Icon Theme]\nDirectories=apps\n", &[]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn icon_theme_in_two_directories_prefers_later_copy() {
+        let root =
+            std::env::temp_dir().join(format!("cosmic-settings-icons-{}", std::process::id()));
+        let (system, user, extra) = (root.join("system"), root.join("user"), root.join("extra"));
+        _ = std::fs::remove_dir_all(&root);
+        let write_theme = |dir: &Path, name: &str, inherits: &str| {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(
+                dir.join("index.theme"),
+                format!("[Icon Theme]\nName={name}\nDirectories=apps\nInherits={inherits}\n"),
+            )
+            .unwrap();
+        };
+
+        write_theme(&system.join("Pop"), "Pop System", "hicolor");
+        // The user's copy is a link to a versioned directory.
+        write_theme(&user.join("Pop-2"), "Pop User", "hicolor");
+        std::os::unix::fs::symlink(user.join("Pop-2"), user.join("Pop")).unwrap();
+        write_theme(&extra.join("Child"), "Child", "Pop");
+
+        let themes = find_icon_themes_in(vec![system, user, extra], &[]).await;
+        _ = std::fs::remove_dir_all(&root);
+
+        let pop: Vec<_> = themes.iter().filter(|theme| theme.id == "Pop").collect();
+        assert_eq!(pop.len(), 1);
+        assert_eq!(pop[0].name, "Pop User");
+
+        // Icons inherited from the theme are looked up in the user's copy.
+        let child = themes.iter().find(|theme| theme.id == "Child").unwrap();
+        assert_eq!(child.inherits, ["Pop-2", "Child"]);
+    }
+
+    #[tokio::test]
+    async fn fallback_and_cursor_themes_are_not_listed() {
+        let root =
+            std::env::temp_dir().join(format!("cosmic-settings-unlisted-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&root);
+        for (id, contents) in [
+            ("hicolor", "[Icon Theme]\nName=Hicolor\nDirectories=apps\n"),
+            ("Cursors", "[Icon Theme]\nName=Cursors\nInherits=hicolor\n"),
+            (
+                "Pop",
+                "[Icon Theme]\nName=Pop\nDirectories=apps\nInherits=hicolor\n",
+            ),
+        ] {
+            std::fs::create_dir_all(root.join(id)).unwrap();
+            std::fs::write(root.join(id).join("index.theme"), contents).unwrap();
+        }
+
+        let themes = find_icon_themes_in(vec![root.clone()], &[]).await;
+        _ = std::fs::remove_dir_all(&root);
+
+        let ids: Vec<_> = themes.iter().map(|theme| theme.id.as_str()).collect();
+        assert_eq!(ids, ["Pop"]);
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io;
+use std::path::PathBuf;
+
+use cosmic::config::CosmicTk;
+use cosmic::cosmic_theme::{ThemeBuilder, ThemeMode};
+use serde::{Deserialize, Serialize};
+
+/// A named look, holding the themes of both modes, the theme mode, and the toolkit
+/// settings such as the icon theme. Panel roundness follows the themes' corner radii.
+///
+/// Settings which are not part of the COSMIC theme are kept alongside, and are missing
+/// from profiles saved before they were added.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LookProfile {
+    pub theme_mode: ThemeMode,
+    pub light: ThemeBuilder,
+    pub dark: ThemeBuilder,
+    pub tk: CosmicTk,
+    /// ID of the default cursor theme.
+    #[serde(default)]
+    pub cursor_theme: Option<String>,
+    #[serde(default)]
+    pub interface_font: Option<String>,
+    #[serde(default)]
+    pub monospace_font: Option<String>,
+    /// GTK theme of GNOME applications.
+    #[serde(default)]
+    pub gtk_theme: Option<String>,
+}
+
+fn dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cosmic-settings").join("look-profiles"))
+}
+
+fn path(name: &str) -> io::Result<PathBuf> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid look profile name",
+        ));
+    }
+
+    dir()
+        .map(|dir| dir.join(format!("{name}.ron")))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))
+}
+
+/// Names of the stored look profiles, in alphabetical order.
+#[must_use]
+pub fn list() -> Vec<String> {
+    let Some(read_dir) = dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "ron" {
+                return None;
+            }
+
+            path.file_stem()?.to_str().map(String::from)
+        })
+        .collect();
+
+    names.sort_unstable_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Stores a look profile, replacing any profile with the same name.
+///
+/// # Errors
+///
+/// Returns an error if the name is invalid, or the profile could not be serialized or written.
+pub fn save(name: &str, profile: &LookProfile) -> io::Result<()> {
+    let path = path(name)?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let contents = ron::ser::to_string_pretty(profile, ron::ser::PrettyConfig::default())
+        .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+
+    std::fs::write(path, contents)
+}
+
+/// Removes a stored look profile.
+///
+/// # Errors
+///
+/// Returns an error if the name is invalid, or the profile could not be removed.
+pub fn delete(name: &str) -> io::Result<()> {
+    std::fs::remove_file(path(name)?)
+}
+
+/// Reads a stored look profile.
+///
+/// # Errors
+///
+/// Returns an error if the profile could not be read or deserialized.
+pub fn load(name: &str) -> io::Result<LookProfile> {
+    let contents = std::fs::read_to_string(path(name)?)?;
+    ron::de::from_str(&contents).map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::snapshot::Snapshot;
+    use super::*;
+
+    #[test]
+    fn profile_without_extra_settings_loads() {
+        let snapshot = Snapshot {
+            theme_mode: ThemeMode::default(),
+            light: ThemeBuilder::light(),
+            dark: ThemeBuilder::dark(),
+            tk: CosmicTk::default(),
+        };
+        let contents = ron::ser::to_string(&snapshot).unwrap();
+
+        let profile: LookProfile = ron::de::from_str(&contents).unwrap();
+
+        assert_eq!(profile.dark, snapshot.dark);
+        assert_eq!(profile.cursor_theme, None);
+        assert_eq!(profile.interface_font, None);
+        assert_eq!(profile.monospace_font, None);
+        assert_eq!(profile.gtk_theme, None);
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use base64::Engine;
+use cosmic::cosmic_theme::ThemeBuilder;
+use cosmic::iced_runtime::core::image::Handle as ImageHandle;
+use qrcode::{EcLevel, QrCode};
+
+/// Longest theme code which still produces a QR code that phones can reliably scan.
+const MAX_THEME_CODE_LEN: usize = 1800;
+
+/// Pixels per QR module.
+const MODULE_SIZE: usize = 4;
+
+/// Light modules surrounding the code, as required by the QR specification.
+const QUIET_ZONE: usize = 4;
+
+/// A theme encoded as base64 RON, for sharing as text or a QR code.
+///
+/// Returns `None` if the theme is too large to fit in a scannable QR code.
+#[must_use]
+pub fn theme_code(builder: &ThemeBuilder) -> Option<String> {
+    let ron = ron::ser::to_string(builder).ok()?;
+    let code = base64::engine::general_purpose::STANDARD.encode(ron);
+    (code.len() <= MAX_THEME_CODE_LEN).then_some(code)
+}
+
+/// Renders text as a black on white QR code image.
+#[must_use]
+pub fn render(text: &str) -> Option<ImageHandle> {
+    let code = QrCode::with_error_correction_level(text, EcLevel::L).ok()?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let size = (modules + QUIET_ZONE * 2) * MODULE_SIZE;
+
+    let mut pixels = vec![0xFF; size * size * 4];
+
+    for (i, color) in colors.into_iter().enumerate() {
+        if color != qrcode::Color::Dark {
+            continue;
+        }
+
+        let x = (i % modules + QUIET_ZONE) * MODULE_SIZE;
+        let y = (i / modules + QUIET_ZONE) * MODULE_SIZE;
+
+        for row in y..y + MODULE_SIZE {
+            let start = (row * size + x) * 4;
+            for pixel in pixels[start..start + MODULE_SIZE * 4].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&[0, 0, 0, 0xFF]);
+            }
+        }
+    }
+
+    Some(ImageHandle::from_pixels(size as u32, size as u32, pixels))
+}
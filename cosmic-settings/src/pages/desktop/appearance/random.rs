@@ -0,0 +1,63 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, Srgb};
+
+/// The least difference in hue between consecutive random accents, in degrees.
+const MIN_HUE_DISTANCE: f32 = 45.0;
+
+/// A random value in the range `0.0..1.0`.
+fn unit() -> f32 {
+    // Every `RandomState` is seeded with new random keys.
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// A random value in the given range.
+fn range(min: f32, max: f32) -> f32 {
+    min + unit() * (max - min)
+}
+
+/// A random color with saturation and value in the given ranges.
+fn hsv(saturation: (f32, f32), value: (f32, f32)) -> Srgb {
+    Srgb::from_color(Hsv::new(
+        range(0.0, 360.0),
+        range(saturation.0, saturation.1),
+        range(value.0, value.1),
+    ))
+}
+
+/// A saturated accent that reads well on both light and dark backgrounds.
+pub fn accent() -> Srgb {
+    hsv((0.45, 0.75), (0.65, 0.85))
+}
+
+/// A random accent whose hue is far enough from the previous accent to be told apart,
+/// so that randomizing again never lands on a similar color.
+pub fn accent_unlike(previous: Option<Srgb>) -> Srgb {
+    let Some(previous) = previous else {
+        return accent();
+    };
+
+    let hue = Hsv::from_color(previous).hue.into_positive_degrees();
+    let mut color = Hsv::from_color(accent());
+    color.hue = (hue + range(MIN_HUE_DISTANCE, 360.0 - MIN_HUE_DISTANCE)).into();
+    Srgb::from_color(color)
+}
+
+/// A muted background appropriate for the color mode.
+pub fn background(is_dark: bool) -> Srgb {
+    if is_dark {
+        hsv((0.05, 0.2), (0.1, 0.2))
+    } else {
+        hsv((0.02, 0.1), (0.9, 0.97))
+    }
+}
+
+/// A subtle tint for text and control components.
+pub fn tint() -> Srgb {
+    hsv((0.1, 0.3), (0.5, 0.9))
+}
@@ -0,0 +1,253 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::Srgba;
+use cosmic::iced_core::{Background, Border, Color, Length};
+use cosmic::widget::{self, container, flex_row, horizontal_space, icon, row, text};
+use cosmic::{Apply, Element};
+
+/// Curated application layouts for previewing a theme in context.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SampleKind {
+    #[default]
+    TextEditor,
+    FileManager,
+}
+
+impl SampleKind {
+    pub const ALL: [Self; 2] = [Self::TextEditor, Self::FileManager];
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::TextEditor => fl!("sample-app", "text-editor"),
+            Self::FileManager => fl!("sample-app", "file-manager"),
+        }
+    }
+}
+
+const EDITOR_LINES: [&str; 7] = [
+    "fn main() {",
+    "    let name = \"COSMIC\";",
+    "",
+    "    for n in 1..=3 {",
+    "        println!(\"{n}: Hello, {name}!\");",
+    "    }",
+    "}",
+];
+
+/// Index of the editor line which is shown as selected.
+const EDITOR_SELECTED_LINE: usize = 4;
+
+const FOLDERS: [&str; 6] = [
+    "Desktop",
+    "Documents",
+    "Downloads",
+    "Music",
+    "Pictures",
+    "Videos",
+];
+
+/// Index of the folder which is shown as selected.
+const SELECTED_FOLDER: usize = 1;
+
+/// A sample application window, styled by the active theme.
+///
+/// Every color and radius is read from the session theme when drawn, so the
+/// sample follows edits and previews as they are applied.
+pub fn view<'a, Message: 'static>(kind: SampleKind) -> Element<'a, Message> {
+    let content = match kind {
+        SampleKind::TextEditor => text_editor(),
+        SampleKind::FileManager => file_manager(),
+    };
+
+    widget::column::with_capacity(2)
+        .push(header(kind.title()))
+        .push(content)
+        .apply(container)
+        .width(Length::Fill)
+        .style(window_style(|theme| &theme.background))
+        .into()
+}
+
+fn header<'a, Message: 'static>(title: String) -> Element<'a, Message> {
+    row::with_capacity(3)
+        .push(text::heading(title))
+        .push(horizontal_space(Length::Fill))
+        .push(icon::from_name("window-close-symbolic").size(16).icon())
+        .align_items(cosmic::iced_core::Alignment::Center)
+        .padding([8, 12])
+        .into()
+}
+
+fn text_editor<'a, Message: 'static>() -> Element<'a, Message> {
+    EDITOR_LINES
+        .into_iter()
+        .enumerate()
+        .fold(
+            widget::column::with_capacity(EDITOR_LINES.len()),
+            |column, (n, line)| {
+                let line = row::with_capacity(2)
+                    .push(text::caption((n + 1).to_string()).width(Length::Fixed(24.0)))
+                    .push(text::body(line));
+
+                column.push(if n == EDITOR_SELECTED_LINE {
+                    line.apply(container)
+                        .width(Length::Fill)
+                        .style(selected_style())
+                        .into()
+                } else {
+                    Element::from(line)
+                })
+            },
+        )
+        .spacing(2)
+        .padding(12)
+        .apply(container)
+        .width(Length::Fill)
+        .style(surface_style(|theme| &theme.primary))
+        .into()
+}
+
+fn file_manager<'a, Message: 'static>() -> Element<'a, Message> {
+    let sidebar = FOLDERS
+        .into_iter()
+        .enumerate()
+        .fold(
+            widget::column::with_capacity(FOLDERS.len()),
+            |column, (n, name)| {
+                let item = row::with_capacity(2)
+                    .push(icon::from_name("folder-symbolic").size(16).icon())
+                    .push(text::body(name))
+                    .spacing(8)
+                    .padding([4, 8])
+                    .apply(container)
+                    .width(Length::Fill);
+
+                column.push(if n == SELECTED_FOLDER {
+                    item.style(accent_style())
+                } else {
+                    item
+                })
+            },
+        )
+        .spacing(2)
+        .padding(8)
+        .apply(container)
+        .width(Length::Fixed(140.0))
+        .height(Length::Fill);
+
+    let files = FOLDERS
+        .into_iter()
+        .enumerate()
+        .map(|(n, name)| {
+            let item = widget::column::with_capacity(2)
+                .push(icon::from_name("folder").size(48).icon())
+                .push(text::caption(name))
+                .spacing(4)
+                .align_items(cosmic::iced_core::Alignment::Center)
+                .width(Length::Fixed(80.0))
+                .apply(container)
+                .padding(4);
+
+            if n == SELECTED_FOLDER {
+                item.style(selected_style()).into()
+            } else {
+                item.into()
+            }
+        })
+        .collect::<Vec<Element<'a, Message>>>();
+
+    let files = flex_row(files)
+        .row_spacing(8)
+        .column_spacing(8)
+        .apply(container)
+        .padding(12)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(surface_style(|theme| &theme.primary));
+
+    row::with_capacity(2)
+        .push(sidebar)
+        .push(files)
+        .height(Length::Fixed(220.0))
+        .into()
+}
+
+fn color(srgba: Srgba) -> Color {
+    Color::from_rgba(srgba.red, srgba.green, srgba.blue, srgba.alpha)
+}
+
+/// The outer frame of a sample window.
+fn window_style(
+    layer: fn(&cosmic::cosmic_theme::Theme) -> &cosmic::cosmic_theme::Container,
+) -> cosmic::theme::Container {
+    cosmic::theme::Container::custom(move |theme| {
+        let cosmic = theme.cosmic();
+        let layer = layer(cosmic);
+        container::Appearance {
+            icon_color: Some(color(layer.on)),
+            text_color: Some(color(layer.on)),
+            background: Some(Background::Color(color(layer.base))),
+            border: Border {
+                color: color(layer.divider),
+                width: 1.0,
+                radius: cosmic.corner_radii.radius_s.into(),
+            },
+            ..Default::default()
+        }
+    })
+}
+
+/// The content area of a sample window.
+fn surface_style(
+    layer: fn(&cosmic::cosmic_theme::Theme) -> &cosmic::cosmic_theme::Container,
+) -> cosmic::theme::Container {
+    cosmic::theme::Container::custom(move |theme| {
+        let cosmic = theme.cosmic();
+        let layer = layer(cosmic);
+        container::Appearance {
+            icon_color: Some(color(layer.on)),
+            text_color: Some(color(layer.on)),
+            background: Some(Background::Color(color(layer.base))),
+            border: Border {
+                radius: cosmic.corner_radii.radius_s.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+}
+
+/// An active item, such as the current location in a sidebar.
+fn accent_style() -> cosmic::theme::Container {
+    cosmic::theme::Container::custom(|theme| {
+        let cosmic = theme.cosmic();
+        container::Appearance {
+            icon_color: Some(color(cosmic.accent.on)),
+            text_color: Some(color(cosmic.accent.on)),
+            background: Some(Background::Color(color(cosmic.accent.base))),
+            border: Border {
+                radius: cosmic.corner_radii.radius_m.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+}
+
+/// Selected text or items, which use the selection color.
+fn selected_style() -> cosmic::theme::Container {
+    cosmic::theme::Container::custom(|theme| {
+        let cosmic = theme.cosmic();
+        container::Appearance {
+            text_color: Some(color(cosmic.accent.selected_text)),
+            background: Some(Background::Color(color(cosmic.accent.selected))),
+            border: Border {
+                radius: cosmic.corner_radii.radius_xs.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+}
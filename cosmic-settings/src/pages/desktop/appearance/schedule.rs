@@ -0,0 +1,184 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, NaiveTime, TimeZone, Timelike};
+use cosmic::cosmic_config::{Config, ConfigGet, ConfigSet};
+use serde::{Deserialize, Serialize};
+
+/// Theme mode config key of the time at which light mode starts.
+const LIGHT_TIME: &str = "auto_switch_light_time";
+/// Theme mode config key of the time at which dark mode starts.
+const DARK_TIME: &str = "auto_switch_dark_time";
+
+/// Minutes which the minute of a time steps by.
+const MINUTE_STEP: u8 = 5;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct TimeOfDay {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl TimeOfDay {
+    #[must_use]
+    pub const fn new(hour: u8, minute: u8) -> Self {
+        Self { hour, minute }
+    }
+
+    /// Moves the hour forward or back by one, wrapping around midnight.
+    #[must_use]
+    pub fn step_hour(self, forward: bool) -> Self {
+        let hour = if forward {
+            (self.hour + 1) % 24
+        } else {
+            (self.hour + 23) % 24
+        };
+
+        Self { hour, ..self }
+    }
+
+    /// Moves the minute forward or back to the next multiple of the step, wrapping
+    /// around the hour.
+    #[must_use]
+    pub fn step_minute(self, forward: bool) -> Self {
+        let minute = if forward {
+            (self.minute / MINUTE_STEP + 1) * MINUTE_STEP % 60
+        } else if self.minute % MINUTE_STEP != 0 {
+            self.minute / MINUTE_STEP * MINUTE_STEP
+        } else {
+            (self.minute + 60 - MINUTE_STEP) % 60
+        };
+
+        Self { minute, ..self }
+    }
+
+    fn from_naive(time: NaiveTime) -> Self {
+        Self::new(time.hour() as u8, time.minute() as u8)
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// Custom times at which auto-switch changes the theme mode, used instead of
+/// sunrise and sunset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Schedule {
+    pub light: TimeOfDay,
+    pub dark: TimeOfDay,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            light: TimeOfDay::new(7, 0),
+            dark: TimeOfDay::new(19, 0),
+        }
+    }
+}
+
+impl Schedule {
+    /// Switches at today's sunrise and sunset at a location.
+    #[must_use]
+    pub fn solar(latitude: f64, longitude: f64) -> Self {
+        let today = chrono::Local::now().date_naive();
+        let (sunrise, sunset) = solar_times(latitude, longitude, today);
+
+        Self {
+            light: TimeOfDay::from_naive(sunrise),
+            dark: TimeOfDay::from_naive(sunset),
+        }
+    }
+
+    /// Reads the schedule from the theme mode config, if custom times are set.
+    #[must_use]
+    pub fn load(config: &Config) -> Option<Self> {
+        let light = config.get::<Option<TimeOfDay>>(LIGHT_TIME).ok()??;
+        let dark = config.get::<Option<TimeOfDay>>(DARK_TIME).ok()??;
+        Some(Self { light, dark })
+    }
+
+    /// Writes the schedule to the theme mode config, or clears it to follow sunrise
+    /// and sunset again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config could not be written.
+    pub fn store(
+        schedule: Option<Self>,
+        config: &Config,
+    ) -> Result<(), cosmic::cosmic_config::Error> {
+        config.set(LIGHT_TIME, schedule.map(|schedule| schedule.light))?;
+        config.set(DARK_TIME, schedule.map(|schedule| schedule.dark))
+    }
+
+    /// Whether it is currently light mode's part of the day.
+    #[must_use]
+    pub fn is_day(&self) -> bool {
+        let now = TimeOfDay::from_naive(chrono::Local::now().time());
+
+        if self.light <= self.dark {
+            self.light <= now && now < self.dark
+        } else {
+            now >= self.light || now < self.dark
+        }
+    }
+}
+
+/// The local times of sunrise and sunset at a location on a date.
+#[must_use]
+pub fn solar_times(latitude: f64, longitude: f64, date: NaiveDate) -> (NaiveTime, NaiveTime) {
+    solar_times_in(latitude, longitude, date, &chrono::Local)
+}
+
+/// The times of sunrise and sunset at a location on a date, in a time zone.
+fn solar_times_in<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+    timezone: &Tz,
+) -> (NaiveTime, NaiveTime) {
+    let (sunrise, sunset) =
+        sunrise::sunrise_sunset(latitude, longitude, date.year(), date.month(), date.day());
+
+    let local_time = |timestamp: i64| {
+        timezone
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .map(|time| time.time())
+            .unwrap_or_default()
+    };
+
+    (local_time(sunrise), local_time(sunset))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+
+    use super::*;
+
+    /// Asserts that a time is within a few minutes of the expected hour and minute.
+    fn assert_near(time: NaiveTime, hour: u32, minute: u32) {
+        let expected = NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        let difference = (time - expected).num_minutes().abs();
+        assert!(difference <= 5, "{time} is not near {expected}");
+    }
+
+    #[test]
+    fn known_sunrise_and_sunset() {
+        // New York City on the June solstice of 2024, in Eastern Daylight Time, when the
+        // sun rose at 05:25 and set at 20:31.
+        let date = NaiveDate::from_ymd_opt(2024, 6, 20).unwrap();
+        let edt = FixedOffset::west_opt(4 * 3600).unwrap();
+        let (sunrise, sunset) = solar_times_in(40.7128, -74.0060, date, &edt);
+
+        assert_near(sunrise, 5, 25);
+        assert_near(sunset, 20, 31);
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, Srgb};
+
+/// Theme colors derived from a single seed color.
+///
+/// Every color shares the hue of the seed, and is taken from a tone of that
+/// hue which suits its role, similar to a Material You tonal palette.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeedPalette {
+    pub accent: Srgb,
+    pub background: Srgb,
+    pub container: Srgb,
+    pub neutral: Srgb,
+    pub text: Srgb,
+}
+
+impl SeedPalette {
+    #[must_use]
+    pub fn new(seed: Srgb, is_dark: bool) -> Self {
+        let seed = Hsv::from_color(seed);
+        let hue = seed.hue;
+        let tone = |saturation: f32, value: f32| Srgb::from_color(Hsv::new(hue, saturation, value));
+
+        // Keep the seed's own character for the accent, but within a range that stays legible.
+        let accent = Srgb::from_color(Hsv::new(
+            hue,
+            seed.saturation.clamp(0.45, 0.85),
+            seed.value.clamp(0.65, 0.9),
+        ));
+
+        if is_dark {
+            Self {
+                accent,
+                background: tone(0.2, 0.12),
+                container: tone(0.18, 0.18),
+                neutral: tone(0.12, 0.55),
+                text: tone(0.08, 0.85),
+            }
+        } else {
+            Self {
+                accent,
+                background: tone(0.06, 0.95),
+                container: tone(0.08, 0.9),
+                neutral: tone(0.12, 0.45),
+                text: tone(0.15, 0.25),
+            }
+        }
+    }
+
+    /// The colors of the palette, from most to least prominent.
+    #[must_use]
+    pub fn colors(&self) -> [Srgb; 5] {
+        [
+            self.accent,
+            self.background,
+            self.container,
+            self.neutral,
+            self.text,
+        ]
+    }
+}
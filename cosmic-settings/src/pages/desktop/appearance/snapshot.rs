@@ -0,0 +1,106 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use cosmic::config::CosmicTk;
+use cosmic::cosmic_theme::{ThemeBuilder, ThemeMode};
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds since the Unix epoch at which a snapshot was taken.
+pub type SnapshotId = i64;
+
+/// Oldest snapshots are removed once there are more than this many.
+const MAX_SNAPSHOTS: usize = 20;
+
+/// The complete appearance state at a point in time.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Snapshot {
+    pub theme_mode: ThemeMode,
+    pub light: ThemeBuilder,
+    pub dark: ThemeBuilder,
+    pub tk: CosmicTk,
+}
+
+fn dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("cosmic-settings").join("appearance-snapshots"))
+}
+
+fn path(id: SnapshotId) -> Option<PathBuf> {
+    dir().map(|dir| dir.join(format!("{id}.ron")))
+}
+
+fn no_cache_dir() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "no cache directory")
+}
+
+/// IDs of the stored snapshots, from newest to oldest.
+#[must_use]
+pub fn list() -> Vec<SnapshotId> {
+    let Some(read_dir) = dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut ids: Vec<SnapshotId> = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "ron" {
+                return None;
+            }
+
+            path.file_stem()?.to_str()?.parse().ok()
+        })
+        .collect();
+
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids
+}
+
+/// Stores a snapshot, and removes the oldest snapshots beyond the limit.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot could not be serialized or written.
+pub fn save(snapshot: &Snapshot) -> io::Result<SnapshotId> {
+    let dir = dir().ok_or_else(no_cache_dir)?;
+    std::fs::create_dir_all(&dir)?;
+
+    let id = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as SnapshotId);
+
+    let contents = ron::ser::to_string(snapshot)
+        .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+
+    std::fs::write(dir.join(format!("{id}.ron")), contents)?;
+
+    for old in list().into_iter().skip(MAX_SNAPSHOTS) {
+        if let Some(path) = path(old) {
+            _ = std::fs::remove_file(path);
+        }
+    }
+
+    Ok(id)
+}
+
+/// Reads a stored snapshot.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot could not be read or deserialized.
+pub fn load(id: SnapshotId) -> io::Result<Snapshot> {
+    let contents = std::fs::read_to_string(path(id).ok_or_else(no_cache_dir)?)?;
+    ron::de::from_str(&contents).map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))
+}
+
+/// A human readable, local time at which a snapshot was taken.
+#[must_use]
+pub fn timestamp(id: SnapshotId) -> String {
+    chrono::DateTime::from_timestamp_millis(id).map_or_else(
+        || id.to_string(),
+        |time| time.with_timezone(&chrono::Local).format("%c").to_string(),
+    )
+}
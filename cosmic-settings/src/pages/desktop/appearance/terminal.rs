@@ -0,0 +1,211 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt::Write;
+
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, LinSrgb, Srgb, Srgba};
+use cosmic::cosmic_theme::ThemeBuilder;
+
+/// Names of the eight ANSI colors, in palette order.
+const NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// A terminal configuration format which a palette can be exported to.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TerminalFormat {
+    #[default]
+    Alacritty,
+    Kitty,
+    CosmicTerm,
+}
+
+impl TerminalFormat {
+    pub const ALL: [Self; 3] = [Self::Alacritty, Self::Kitty, Self::CosmicTerm];
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Alacritty => fl!("terminal-palette", "alacritty"),
+            Self::Kitty => fl!("terminal-palette", "kitty"),
+            Self::CosmicTerm => fl!("terminal-palette", "cosmic-term"),
+        }
+    }
+
+    /// The name suggested when choosing where to save the palette.
+    #[must_use]
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Alacritty => "cosmic.yml",
+            Self::Kitty => "cosmic.conf",
+            Self::CosmicTerm => "cosmic.ron",
+        }
+    }
+
+    /// Serializes the palette of a theme in this format.
+    #[must_use]
+    pub fn serialize(self, builder: &ThemeBuilder) -> String {
+        let theme = builder.clone().build();
+        let ansi = ansi_palette(builder);
+        let background = hex(theme.background.base.color);
+        let foreground = hex(theme.background.on.color);
+
+        let mut out = String::new();
+        match self {
+            Self::Alacritty => {
+                _ = writeln!(out, "colors:\n  primary:");
+                _ = writeln!(out, "    background: '{background}'");
+                _ = writeln!(out, "    foreground: '{foreground}'");
+                for (section, colors) in [("normal", &ansi[..8]), ("bright", &ansi[8..])] {
+                    _ = writeln!(out, "  {section}:");
+                    for (name, color) in NAMES.iter().zip(colors) {
+                        _ = writeln!(out, "    {name}: '{}'", hex(*color));
+                    }
+                }
+            }
+            Self::Kitty => {
+                _ = writeln!(out, "background {background}");
+                _ = writeln!(out, "foreground {foreground}");
+                for (n, color) in ansi.iter().enumerate() {
+                    _ = writeln!(out, "color{n} {}", hex(*color));
+                }
+            }
+            Self::CosmicTerm => {
+                _ = writeln!(out, "(\n    name: \"COSMIC\",");
+                _ = writeln!(out, "    foreground: \"{foreground}\",");
+                _ = writeln!(out, "    background: \"{background}\",");
+                _ = writeln!(out, "    cursor: \"{}\",", hex(theme.accent.base.color));
+                _ = writeln!(out, "    bright_foreground: \"{foreground}\",");
+                _ = writeln!(out, "    dim_foreground: \"{}\",", hex(ansi[8]));
+                for (section, colors) in [("normal", &ansi[..8]), ("bright", &ansi[8..])] {
+                    _ = writeln!(out, "    {section}: (");
+                    for (name, color) in NAMES.iter().zip(colors) {
+                        _ = writeln!(out, "        {name}: \"{}\",", hex(*color));
+                    }
+                    _ = writeln!(out, "    ),");
+                }
+                _ = writeln!(out, "    dim: (");
+                for (name, color) in NAMES.iter().zip(&ansi[..8]) {
+                    _ = writeln!(out, "        {name}: \"{}\",", hex(shade(*color, 0.7)));
+                }
+                _ = writeln!(out, "    ),\n)");
+            }
+        }
+
+        out
+    }
+}
+
+/// The 16 ANSI colors of a theme: the normal colors, then their bright variants.
+///
+/// Colors come from the accent palette, and the grays from its neutrals, which carry
+/// the neutral tint of the theme.
+#[must_use]
+pub fn ansi_palette(builder: &ThemeBuilder) -> [Srgb; 16] {
+    let palette = builder.clone().build().palette;
+    let [black, gray, silver, white] = by_luminance([
+        palette.neutral_1,
+        palette.neutral_4,
+        palette.neutral_7,
+        palette.neutral_9,
+    ]);
+
+    let red = palette.accent_red.color;
+    let green = palette.accent_green.color;
+    let yellow = palette.accent_yellow.color;
+    let blue = palette.accent_blue.color;
+    let magenta = palette.accent_purple.color;
+    // The accent palette has no cyan, which sits between its blue and green.
+    let cyan = Srgb::from_linear((blue.into_linear::<f32>() + green.into_linear()) / 2.0);
+
+    let normal = [black, red, green, yellow, blue, magenta, cyan, silver];
+    let mut colors = [Srgb::new(0.0, 0.0, 0.0); 16];
+    colors[..8].copy_from_slice(&normal);
+    colors[8] = gray;
+    for (bright, color) in colors[9..15].iter_mut().zip(&normal[1..7]) {
+        *bright = brighten(*color);
+    }
+    colors[15] = white;
+
+    colors
+}
+
+/// Orders colors from darkest to lightest, as light palettes order neutrals in reverse.
+fn by_luminance(colors: [Srgba; 4]) -> [Srgb; 4] {
+    let mut colors = colors.map(|color| color.color);
+    colors.sort_by(|a, b| luminance(*a).total_cmp(&luminance(*b)));
+    colors
+}
+
+fn luminance(color: Srgb) -> f32 {
+    let LinSrgb {
+        red, green, blue, ..
+    } = color.into_linear();
+    0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
+
+fn brighten(color: Srgb) -> Srgb {
+    let mut hsv = Hsv::from_color(color);
+    hsv.saturation *= 0.85;
+    hsv.value = (hsv.value + (1.0 - hsv.value) * 0.5).min(1.0);
+    Srgb::from_color(hsv)
+}
+
+fn shade(color: Srgb, factor: f32) -> Srgb {
+    let mut hsv = Hsv::from_color(color);
+    hsv.value *= factor;
+    Srgb::from_color(hsv)
+}
+
+fn hex(color: Srgb) -> String {
+    let Srgb {
+        red, green, blue, ..
+    } = color.into_format::<u8>();
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::contrast::contrast_ratio;
+    use super::*;
+
+    fn assert_mapping(builder: &ThemeBuilder) {
+        let palette = builder.clone().build().palette;
+        let ansi = ansi_palette(builder);
+
+        assert_eq!(ansi[1], palette.accent_red.color);
+        assert_eq!(ansi[2], palette.accent_green.color);
+        assert_eq!(ansi[3], palette.accent_yellow.color);
+        assert_eq!(ansi[4], palette.accent_blue.color);
+        assert_eq!(ansi[5], palette.accent_purple.color);
+
+        // Black, the bright black gray, silver, and white get lighter in that order.
+        let grays = [ansi[0], ansi[8], ansi[7], ansi[15]].map(luminance);
+        assert!(grays.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        for (normal, bright) in ansi[1..7].iter().zip(&ansi[9..15]) {
+            assert!(luminance(*bright) >= luminance(*normal));
+        }
+    }
+
+    #[test]
+    fn dark_mapping() {
+        assert_mapping(&ThemeBuilder::dark());
+    }
+
+    #[test]
+    fn light_mapping() {
+        assert_mapping(&ThemeBuilder::light());
+    }
+
+    #[test]
+    fn text_contrasts_with_background() {
+        for (builder, is_dark) in [(ThemeBuilder::dark(), true), (ThemeBuilder::light(), false)] {
+            let background = builder.clone().build().background.base.color;
+            let ansi = ansi_palette(&builder);
+            // White is the text of dark terminals, and black the text of light ones.
+            let text = if is_dark { ansi[15] } else { ansi[0] };
+            assert!(contrast_ratio(text, background) >= 4.5);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::Srgba;
+use cosmic::cosmic_theme::ThemeBuilder;
+use cosmic::iced_core::{Background, Border, Color, Length};
+use cosmic::widget::{container, row, text};
+use cosmic::{Apply, Element};
+
+const SWATCH_SIZE: f32 = 12.0;
+
+/// The colors and roundness of a theme, for drawing a small preview of it.
+///
+/// Building a theme is too costly to do while drawing, so thumbnails should be cached.
+#[derive(Clone, Copy, Debug)]
+pub struct ThemeThumbnail {
+    background: Color,
+    container: Color,
+    accent: Color,
+    on_accent: Color,
+    text: Color,
+    radius: [f32; 4],
+}
+
+impl ThemeThumbnail {
+    #[must_use]
+    pub fn new(builder: ThemeBuilder) -> Self {
+        let theme = builder.build();
+
+        Self {
+            background: color(theme.background.base),
+            container: color(theme.primary.base),
+            accent: color(theme.accent.base),
+            on_accent: color(theme.accent.on),
+            text: color(theme.background.on),
+            radius: theme.corner_radii.radius_s,
+        }
+    }
+
+    /// Swatches of the theme's colors beside a sample button, on the theme's background.
+    pub fn view<'a, Message: 'static>(self) -> Element<'a, Message> {
+        let swatch = move |fill: Color| {
+            cosmic::widget::Space::new(Length::Fixed(SWATCH_SIZE), Length::Fixed(SWATCH_SIZE))
+                .apply(container)
+                .style(fill_style(fill, self.text, [SWATCH_SIZE / 2.0; 4]))
+        };
+
+        let button = text::caption("Aa")
+            .apply(container)
+            .padding([0, 6])
+            .style(fill_style(self.accent, self.on_accent, self.radius));
+
+        row::with_capacity(4)
+            .push(swatch(self.container))
+            .push(swatch(self.accent))
+            .push(swatch(self.text))
+            .push(button)
+            .spacing(4)
+            .align_items(cosmic::iced_core::Alignment::Center)
+            .apply(container)
+            .padding(4)
+            .style(fill_style(self.background, self.text, self.radius))
+            .into()
+    }
+}
+
+fn color(srgba: Srgba) -> Color {
+    Color::from_rgba(srgba.red, srgba.green, srgba.blue, srgba.alpha)
+}
+
+fn fill_style(fill: Color, on: Color, radius: [f32; 4]) -> cosmic::theme::Container {
+    cosmic::theme::Container::custom(move |_| container::Appearance {
+        icon_color: Some(on),
+        text_color: Some(on),
+        background: Some(Background::Color(fill)),
+        border: Border {
+            radius: radius.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
@@ -0,0 +1,80 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_theme::palette::{LinSrgb, Srgb};
+use cosmic::iced_core::Color;
+
+/// A color vision deficiency to simulate in color previews.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorVisionMode {
+    #[default]
+    Normal,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorVisionMode {
+    pub const ALL: [Self; 4] = [
+        Self::Normal,
+        Self::Protanopia,
+        Self::Deuteranopia,
+        Self::Tritanopia,
+    ];
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Normal => fl!("color-vision", "normal"),
+            Self::Protanopia => fl!("color-vision", "protanopia"),
+            Self::Deuteranopia => fl!("color-vision", "deuteranopia"),
+            Self::Tritanopia => fl!("color-vision", "tritanopia"),
+        }
+    }
+}
+
+type Matrix = [[f32; 3]; 3];
+
+/// Linear RGB to LMS cone responses, from Viénot, Brettel and Mollon (1999).
+const RGB_TO_LMS: Matrix = [
+    [17.8824, 43.5161, 4.11935],
+    [3.45565, 27.1554, 3.86714],
+    [0.029_956_6, 0.184_309, 1.46709],
+];
+
+const LMS_TO_RGB: Matrix = [
+    [0.080_944_45, -0.130_504_41, 0.116_721_07],
+    [-0.010_248_533, 0.054_019_33, -0.113_614_71],
+    [-0.000_365_296_94, -0.004_121_614_7, 0.693_511_4],
+];
+
+/// Replaces the response of the missing cone with one derived from the remaining cones.
+const PROTANOPIA: Matrix = [[0.0, 2.02344, -2.52581], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+const DEUTERANOPIA: Matrix = [[1.0, 0.0, 0.0], [0.494_207, 0.0, 1.24827], [0.0, 0.0, 1.0]];
+const TRITANOPIA: Matrix = [
+    [1.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [-0.395_913, 0.801_109, 0.0],
+];
+
+fn mul(matrix: &Matrix, [a, b, c]: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * a + row[1] * b + row[2] * c)
+}
+
+/// How a color appears to someone with the given color vision.
+#[must_use]
+pub fn simulate(color: Color, mode: ColorVisionMode) -> Color {
+    let deficiency = match mode {
+        ColorVisionMode::Normal => return color,
+        ColorVisionMode::Protanopia => &PROTANOPIA,
+        ColorVisionMode::Deuteranopia => &DEUTERANOPIA,
+        ColorVisionMode::Tritanopia => &TRITANOPIA,
+    };
+
+    let linear = Srgb::new(color.r, color.g, color.b).into_linear();
+    let lms = mul(&RGB_TO_LMS, [linear.red, linear.green, linear.blue]);
+    let [r, g, b] = mul(&LMS_TO_RGB, mul(deficiency, lms)).map(|v| v.clamp(0.0, 1.0));
+    let simulated = Srgb::from_linear(LinSrgb::new(r, g, b));
+
+    Color::from_rgba(simulated.red, simulated.green, simulated.blue, color.a)
+}
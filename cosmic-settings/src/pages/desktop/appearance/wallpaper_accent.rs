@@ -0,0 +1,131 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::{Path, PathBuf};
+
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, Srgb};
+use cosmic_bg_config::Source;
+
+/// The most accent candidates offered for a wallpaper.
+pub const MAX_ACCENTS: usize = 5;
+
+/// Number of color clusters the wallpaper is reduced to.
+const CLUSTERS: usize = 8;
+const ITERATIONS: usize = 10;
+/// Wallpapers are downscaled to this size before being sampled.
+const SAMPLE_SIZE: u32 = 64;
+/// Clusters less saturated than this have no meaningful hue.
+const MIN_SATURATION: f32 = 0.15;
+/// Candidates with hues closer than this, in degrees, are considered the same accent.
+const MIN_HUE_DISTANCE: f32 = 20.0;
+
+/// The image file of the wallpaper shown on every output, or on the first output.
+///
+/// Slideshows and solid colors have no single image to sample.
+pub fn current_wallpaper() -> Option<PathBuf> {
+    match crate::pages::desktop::wallpaper::Config::new().current_image("all")? {
+        Source::Path(path) if path.is_file() => Some(path),
+        _ => None,
+    }
+}
+
+/// Accent candidates from the dominant colors of an image, most common first.
+pub fn extract(path: &Path) -> Vec<Srgb> {
+    let image = match image::open(path) {
+        Ok(image) => image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).into_rgb8(),
+        Err(why) => {
+            tracing::error!(?why, ?path, "failed to load the wallpaper");
+            return Vec::new();
+        }
+    };
+
+    let pixels = image
+        .pixels()
+        .map(|pixel| pixel.0.map(|channel| f32::from(channel) / 255.0))
+        .collect::<Vec<_>>();
+
+    let mut accents = Vec::<Hsv>::with_capacity(MAX_ACCENTS);
+
+    for [red, green, blue] in clusters(&pixels) {
+        let mut color = Hsv::from_color(Srgb::new(red, green, blue));
+
+        if color.saturation < MIN_SATURATION {
+            continue;
+        }
+
+        let hue = color.hue.into_positive_degrees();
+        let distinct = accents.iter().all(|accent| {
+            let distance = (accent.hue.into_positive_degrees() - hue).abs();
+            distance.min(360.0 - distance) >= MIN_HUE_DISTANCE
+        });
+
+        if distinct {
+            // Keep the same chroma range as random accents, which reads on both color modes.
+            color.saturation = color.saturation.clamp(0.45, 0.75);
+            color.value = color.value.clamp(0.65, 0.85);
+            accents.push(color);
+
+            if accents.len() == MAX_ACCENTS {
+                break;
+            }
+        }
+    }
+
+    accents.into_iter().map(Srgb::from_color).collect()
+}
+
+/// Reduces the pixels to their dominant colors with k-means, largest cluster first.
+fn clusters(pixels: &[[f32; 3]]) -> Vec<[f32; 3]> {
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+
+    // Evenly spaced pixels make a deterministic starting point, so the same wallpaper
+    // always gives the same accents.
+    let mut centroids = (0..CLUSTERS)
+        .map(|n| pixels[n * pixels.len() / CLUSTERS])
+        .collect::<Vec<_>>();
+
+    let mut sizes = vec![0usize; CLUSTERS];
+
+    for _ in 0..ITERATIONS {
+        let mut sums = vec![[0.0f32; 3]; CLUSTERS];
+        sizes.fill(0);
+
+        for pixel in pixels {
+            let nearest = nearest(&centroids, pixel);
+            sizes[nearest] += 1;
+            for channel in 0..3 {
+                sums[nearest][channel] += pixel[channel];
+            }
+        }
+
+        for (centroid, (sum, &size)) in centroids.iter_mut().zip(sums.iter().zip(&sizes)) {
+            if size > 0 {
+                *centroid = sum.map(|channel| channel / size as f32);
+            }
+        }
+    }
+
+    let mut clusters = centroids.into_iter().zip(sizes).collect::<Vec<_>>();
+    clusters.sort_by(|a, b| b.1.cmp(&a.1));
+    clusters
+        .into_iter()
+        .filter(|(_, size)| *size > 0)
+        .map(|(centroid, _)| centroid)
+        .collect()
+}
+
+fn nearest(centroids: &[[f32; 3]], pixel: &[f32; 3]) -> usize {
+    let distance = |centroid: &[f32; 3]| {
+        (0..3)
+            .map(|channel| (centroid[channel] - pixel[channel]).powi(2))
+            .sum::<f32>()
+    };
+
+    centroids
+        .iter()
+        .enumerate()
+        .min_by(|a, b| distance(a.1).total_cmp(&distance(b.1)))
+        .map_or(0, |(index, _)| index)
+}
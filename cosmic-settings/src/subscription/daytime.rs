@@ -44,7 +44,7 @@ async fn inner(mut tx: Sender<bool>) -> anyhow::Result<()> {
         let (lat, long) = (loc.latitude(), loc.longitude());
         let now = chrono::Local::now();
         let date = now.date_naive();
-        let (sunrise, sunset) = sunrise_sunset(lat, long, date.year(), date.month0(), date.day0());
+        let (sunrise, sunset) = sunrise_sunset(lat, long, date.year(), date.month(), date.day());
         let now_in_seconds = now.timestamp();
         let daytime = now_in_seconds >= sunrise && now_in_seconds <= sunset;
         tx.send(daytime).await?;
@@ -60,8 +60,8 @@ async fn inner(mut tx: Sender<bool>) -> anyhow::Result<()> {
                 lat,
                 long,
                 tmrw_date.year(),
-                tmrw_date.month0(),
-                tmrw_date.day0(),
+                tmrw_date.month(),
+                tmrw_date.day(),
             );
             tmrw_sunrise - now_in_seconds
         };
@@ -121,3 +121,24 @@ pub fn sub_page_header<'a, Message: 'static + Clone>(
         .width(Length::Shrink)
         .into()
 }
+
+/// A dismissable notification shown above the page content.
+#[must_use]
+pub fn toast<'a, Message: 'static + Clone>(
+    message: &'a str,
+    on_close: Message,
+) -> Element<'a, Message> {
+    let close_button = button::icon(icon::from_name("window-close-symbolic"))
+        .extra_small()
+        .on_press(on_close);
+
+    row::with_capacity(3)
+        .push(text(message))
+        .push(horizontal_space(Length::Fill))
+        .push(close_button)
+        .align_items(cosmic::iced::Alignment::Center)
+        .apply(container)
+        .padding([8, 16])
+        .style(theme::Container::List)
+        .into()
+}
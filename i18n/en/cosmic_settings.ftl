@@ -14,61 +14,318 @@ appearance = Appearance
     .desc = Accent colors and COSMIC theming.
 
 accent-color = Accent color
+accent-swatch = Accent color: { $name }, { $hex }
+mode-preview = Mode preview
+    .dark = Dark mode preview
+    .light = Light mode preview
+accent-palette = Saved accent colors
+    .add = Add to palette
+    .saved = Saved color. Right-click to remove.
+    .wallpaper = Color from the wallpaper
+    .no-wallpaper = The current wallpaper is not a single image
+accent-name = Accent color
+    .custom = Custom
+    .blue = Blue
+    .indigo = Indigo
+    .purple = Purple
+    .pink = Pink
+    .red = Red
+    .orange = Orange
+    .yellow = Yellow
+    .green = Green
+    .warm-grey = Warm grey
 app-background = Application or window background
+    .opacity = Opacity
+    .gradient = Gradient
+    .use-gradient = Use a gradient
+    .gradient-desc = Applications use the average color of the gradient.
+    .stop = Color { $number }
+    .set-stop = Use picked color
+    .add-stop = Add color
+    .remove-stop = Remove color
 auto = Auto
+cancel = Cancel
 close = Close
 color-picker = Color Picker
 copied-to-clipboard = Copied to clipboard
 copy-to-clipboard = Copy to clipboard
 dark = Dark
 export = Export
+export-both = Export both
 hex = Hex
+hsl = HSL
+hsv = HSV
 import = Import
 light = Light
 mode-and-colors = Mode and Colors
+randomize-colors = Randomize colors
+seed-color = Generate from color
+    .desc = Pick a color to derive a matching theme from.
+    .apply = Apply theme
+color-input = Color components
+    .hue = Hue
+    .saturation = Saturation
+    .lightness = Lightness
+    .value = Value
 recent-colors = Recent colors
+    .saved = Recently used
+    .clear = Clear
 reset-to-default = Reset to default
+    .colors = Colors
+    .layout = Shape and layout
+    .everything = Everything
+restore-defaults = Restore defaults
+    .desc = Both light and dark mode, the icon theme, panel roundness, and fonts return to how COSMIC ships them. Saved accents, recent colors, and looks are deleted for good.
+    .confirm = Restore and delete
 rgb = RGB
+theme = Theme
 window-hint-accent = Active window hint color
+    .link = Follow the accent color
+    .opacity = Opacity
 window-hint-accent-toggle = Use theme accent color as active window hint
+sync-layout = Keep light and dark layout in sync
+    .desc = Roundness, gaps and the active hint are shared by light and dark mode.
+    .dark-only = Roundness, gaps and the active hint set here only apply to dark mode.
+    .light-only = Roundness, gaps and the active hint set here only apply to light mode.
+layout-synced = Synced layout
+    .light = Synced layout to light mode
+    .dark = Synced layout to dark mode
+    .failed = { $count ->
+        [one] A setting couldn't be applied to the other color mode
+       *[other] { $count } settings couldn't be applied to the other color mode
+    }
+decoration-accent-only = Accent window decorations only
+    .desc = Apply the accent color to window hints and decorations, and keep the default accent elsewhere.
+mode-accents = Separate day and night accents
+    .desc = Choose the accent colors of light and dark mode together.
+    .day = Day accent
+    .night = Night accent
+high-contrast = High contrast
+    .desc = Maximizes the contrast of text, controls, and backgrounds in both light and dark mode.
+copy-colors = Copy
+    .from-dark = Copy colors from dark mode
+    .from-light = Copy colors from light mode
+    .desc = Uses the accent, tints, and backgrounds of the other mode. Backgrounds are kept if text on them would be hard to read.
+    .backgrounds-skipped = Backgrounds were not copied, as text on them would be hard to read in this mode.
 
 auto-switch = Automatically switch from Light to Dark mode
     .sunrise = Switches to Light mode at sunrise
     .sunset = Switches to Light mode at sunset
     .next-sunrise = Switches to Light mode at next sunrise
     .next-sunset = Switches to Light mode at next sunset
+    .schedule = Schedule
+    .sunrise-sunset = Sunrise and sunset
+    .custom = Custom times
+    .light-time = Light mode starts at
+    .dark-time = Dark mode starts at
+    .custom-light = Switches to Light mode at { $time }
+    .custom-dark = Switches to Dark mode at { $time }
+    .latitude = Latitude
+    .longitude = Longitude
+    .location-desc = Used to find sunrise and sunset when location services are unavailable.
 
 container-background = Container background
     .desc-detail = Container background color is used for navigation sidebar, side drawer, dialogs and similar widgets. By default, it is automatically derived from the Application or window background.
     .reset = Reset to auto
+    .opacity = Opacity
     .desc = Primary container color is used for navigation sidebar, side drawer, dialogs and similar widgets.
 
 control-tint = Control component tint
     .desc = Used for backgrounds of standard buttons, search inputs, text inputs, and similar components.
 
+selection-color = Selection highlight
+    .desc = Used to highlight selected text and items.
+    .sample = Selected text
+
 frosted = Frosted glass effect on system interface
     .desc = Applies background blur to panel, dock, applets, launcher, and application library.
 
 experimental-settings = Experimental settings
 
+preview-mode = Preview without saving
+    .desc = Try changes on the current session. Nothing is saved until the changes are applied.
+    .apply = Apply changes
+    .discard = Discard changes
+
+snapshots = Snapshots
+    .take = Take a snapshot
+    .desc = Save the theme, icon theme, and toolkit settings to restore them later.
+    .take-button = Take snapshot
+    .restore = Restore
+
+import-scale = Import theme
+    .desc = Themes made for a different display density can be scaled to fit this display.
+    .scale = Scale
+    .summary = Gaps: { $gaps }px, active window hint: { $hint }px, corner radius: { $radius }px
+
+import-preview = Changes
+    .roundness = Corner radius
+    .unchanged = This theme matches the current theme.
+
+icon-scan-excludes = Excluded icon directories
+    .add = Exclude a directory
+    .desc = Skip slow or network directories when searching for icon themes.
+    .add-button = Choose directory
+
+icon-theme-overrides = Application icon themes
+    .add = Use a different icon theme for an application
+    .desc = Applications which read this setting show their icons from the chosen theme.
+    .app-id = Desktop ID
+    .add-button = Add
+
+look-profiles = Looks
+    .save = Save current look
+    .desc = Saves the theme of both modes, the theme mode, the icon theme, and the panel roundness under a name.
+    .name = Name
+    .save-button = Save
+    .load = Switch
+
+confirm-overwrite = Replace file?
+    .desc = A file named "{ $file }" already exists. Replacing it will overwrite its contents.
+    .always = Replace exported files without asking
+    .replace = Replace
+
+sample-app = Sample applications
+    .open = Preview in apps
+    .desc = See how applications look with the current theme.
+    .text-editor = Text Editor
+    .file-manager = Files
+
+theme-qr = Theme QR code
+    .show = Show QR code
+    .desc = Scan this code on another device running COSMIC to use the same theme.
+    .too-large = This theme is too large to share as a QR code.
+
+import-theme = Import theme
+    .success = Theme imported
+    .failed = Could not read theme file
+    .newer = Theme was made for a newer version of COSMIC
+    .older = Theme was made for an older version of COSMIC and is no longer compatible
+    .clipboard = Import from clipboard
+    .empty-clipboard = The clipboard holds no theme
+    .drop = Drop the theme file to import it
+    .not-theme-file = Only .ron theme files can be imported
+recent-themes = Recent theme files
+    .missing = The theme file no longer exists
+
+export-theme = Export theme
+    .success = Exported successfully
+    .failed = Could not write theme file
+    .clipboard = Copy theme
+
+export-gtk-css = GTK stylesheet
+    .desc = Write the theme colors to gtk.css for GTK 3 and GTK 4 applications. A gtk.css of your own is kept as gtk.css.bak.
+
+terminal-palette = Terminal colors
+    .desc = Export the theme colors as a 16 color terminal palette.
+    .alacritty = Alacritty
+    .kitty = Kitty
+    .cosmic-term = COSMIC Terminal
+
+export-diagnostics = Appearance summary
+    .desc = Export the current appearance settings and environment to attach to bug reports.
+
+contrast-standard = Minimum contrast
+    .desc = Contrast ratio that interface colors are checked against.
+    .aa = AA (4.5:1)
+    .aaa = AAA (7:1)
+
+color-vision = Simulate color vision
+    .desc = Show accent colors as they appear with a color vision deficiency. The theme is not changed.
+    .normal = Normal
+    .protanopia = Protanopia
+    .deuteranopia = Deuteranopia
+    .tritanopia = Tritanopia
+
+contrast-pass = Text contrast is { $ratio }:1, which meets the minimum of { $minimum }:1.
+contrast-warning = Text contrast is { $ratio }:1, below the minimum of { $minimum }:1. Text may be hard to read.
+
 enable-export = Apply this theme to GNOME apps.
     .desc = Not all toolkits support auto-switching. Non-COSMIC apps may need to be restarted after a theme change.
 
+gtk-theme = GTK theme
+    .desc = The widget theme of GNOME applications.
+    .failed = The GTK theme could not be applied.
+
 icon-theme = Icon theme
     .desc = Applies a different set of icons to applications.
 
+icon-symbolic-preview = Preview symbolic icons
+    .desc = Show the selected icon theme as it looks in symbolic contexts, where the theme provides symbolic icons.
+icon-diagnostics = Mark missing icons
+    .desc = Outline previewed icons which an icon theme does not provide, and which are shown from another theme.
+
+icon-theme-gnome-failed = The icon theme could not be applied to GTK applications.
+color-scheme-gnome-failed = GTK applications could not be told to follow the dark or light mode.
+
+icon-theme-sort = Sort by
+    .name = Name
+    .id = Directory name
+    .recently-used = Recently used
+    .newest = Newest installed
+
+cursor-theme = Cursor theme
+    .failed = The cursor theme could not be applied.
+
+interface-font = Font
+    .family = Interface font
+    .sample = The quick brown fox jumps over the lazy dog.
+    .monospace = Monospace font
+    .scaling = Text scaling
+    .scaling-size = { $factor }× ({ $points } pt body text)
+    .failed = The font could not be applied to GNOME applications.
+
+icon-theme-filter = Search icon themes
+icon-preview-size = { $size } px
+
+icon-theme-import = Use from theme file
+    .none = The theme file does not include an icon theme.
+    .missing = The icon theme "{ $theme }" is not installed.
+
+theme-read-only = The theme settings on this system are read-only, so changes cannot be saved.
+    .short = Read-only
+
+theme-invalid = The theme could not be applied because it contains invalid colors. The previous theme has been kept.
+
 text-tint = Interface text tint
     .desc = Color used to derive interface text colors that have sufficient contrast on various surfaces.
+    .intensity = Tint intensity
+    .sample = The quick brown fox jumps over the lazy dog. Adjust the intensity until this text is comfortable to read.
+    .apply = Apply intensity
+
+live-preview = Preview
+    .desc = Controls drawn with your changes as you make them.
+    .button = Button
+    .secondary-button = Cancel
+    .text-field = Text field
+    .toggle = Toggle
+    .list-row = List item
 
 style = Style
     .round = Round
     .slightly-round = Slightly round
     .square = Square
+    .square-floating-panel = The floating panel and dock will also have square corners.
+    .custom = Custom roundness
+    .custom-desc = Set each corner radius of the theme.
+    .edit = Edit
+    .radius-xs = Extra small radius
+    .radius-s = Small radius
+    .radius-m = Medium radius
+    .radius-l = Large radius
+    .radius-xl = Extra large radius
+    .link-corners = Link corners
+    .top-left = Top left
+    .top-right = Top right
+    .bottom-right = Bottom right
+    .bottom-left = Bottom left
 
 # interface density left out for now
 window-management = Window Management
     .active-hint = Active window hint size
-    .gaps = Gaps around tiled windows
+    .gaps = Gaps between tiled windows
+    .outer-gaps = Gaps at screen edges
+    .outer-gaps-desc = Space between tiled windows and the edges of the screen.
 
 ## Desktop: Display
 